@@ -59,6 +59,7 @@ pub fn main() -> Result<(), Error> {
         ticker: String::from("BAYN GY Equity"),
         fields: vec!["rt_time_of_trade", "last_price", "bid", "ask"],
         options: Some(options),
+        ..Default::default()
     };
 
     let all_sub = vec![bay_sup];
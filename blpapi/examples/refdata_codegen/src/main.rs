@@ -0,0 +1,60 @@
+use blpapi::{
+    Error,
+    codegen::{RefDataGenerator, read_field_dump, write_field_dump},
+    data_series::FieldSeries,
+    session::{Session, SessionBuilder},
+    session_options::SessionOptions,
+};
+
+fn start_session() -> Result<Session, Error> {
+    let s_opt = SessionOptions::default();
+    let mut session = SessionBuilder::default().options(s_opt).build();
+    session.start()?;
+    Ok(session)
+}
+
+/// Usage: refdata_codegen [--name Data] [--search text] [--dump fields.json] [mnemonic ...]
+/// e.g. refdata_codegen --name Quote PX_LAST VOLUME ID_ISIN
+///
+/// An existing dump is read instead of querying //blp/apiflds,
+/// otherwise the queried field information is written into it.
+pub fn main() -> Result<(), Error> {
+    env_logger::init();
+
+    let mut name = String::from("Data");
+    let mut search: Vec<String> = vec![];
+    let mut dump: Option<String> = None;
+    let mut mnemonics: Vec<String> = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" => name = args.next().unwrap_or(name),
+            "--search" => search.extend(args.next()),
+            "--dump" => dump = args.next(),
+            _ => mnemonics.push(arg),
+        }
+    }
+
+    let fields: Vec<FieldSeries> = match &dump {
+        Some(path) if std::path::Path::new(path).exists() => read_field_dump(path)?,
+        _ => {
+            let mut session = start_session()?;
+            let mut fields = vec![];
+            if !mnemonics.is_empty() {
+                let ids: Vec<&str> = mnemonics.iter().map(String::as_str).collect();
+                fields.extend(session.field_info_for(&ids, None, None)?);
+            }
+            if !search.is_empty() {
+                let search: Vec<&str> = search.iter().map(String::as_str).collect();
+                fields.extend(session.field_search(search, None)?);
+            }
+            if let Some(path) = &dump {
+                write_field_dump(path, &fields)?;
+            }
+            fields
+        }
+    };
+
+    print!("{}", RefDataGenerator::new(name).generate(&fields));
+    Ok(())
+}
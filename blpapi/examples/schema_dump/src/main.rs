@@ -0,0 +1,39 @@
+use blpapi::{
+    Error,
+    schema_dump::dump_service,
+    service::BlpServices,
+    session::{Session, SessionBuilder},
+    session_options::SessionOptions,
+};
+
+fn start_session() -> Result<Session, Error> {
+    let s_opt = SessionOptions::default();
+    let mut session = SessionBuilder::default().options(s_opt).build();
+    session.start()?;
+    Ok(session)
+}
+
+/// Usage: schema_dump <output dir> [service ...]
+/// e.g. schema_dump schemas //blp/refdata //blp/mktdata
+pub fn main() -> Result<(), Error> {
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let dir = args.next().unwrap_or_else(|| String::from("schemas"));
+    let mut services: Vec<BlpServices> = args.map(|s| BlpServices::from(s.as_str())).collect();
+    if services.is_empty() {
+        services = vec![BlpServices::ReferenceData, BlpServices::MarketData];
+    }
+
+    let mut session = start_session()?;
+    for service in &services {
+        let dump = dump_service(&mut session, service, &dir)?;
+        println!(
+            "{} -> {:?}, {:?}",
+            <&str>::from(service),
+            dump.xml,
+            dump.json
+        );
+    }
+    Ok(())
+}
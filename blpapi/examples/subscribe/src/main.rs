@@ -42,6 +42,7 @@ pub fn main() -> Result<(), Error> {
         ticker: String::from("BAYN GY Equity"),
         fields: vec!["rt_time_of_trade", "last_price", "bid", "ask"],
         options: Some(options),
+        ..Default::default()
     };
 
     let options_apl = options!(interval = 2, conflate = "");
@@ -49,6 +50,7 @@ pub fn main() -> Result<(), Error> {
         ticker: String::from("BAS GR Equity"),
         fields: vec!["rt_time_of_trade", "bid"],
         options: Some(options_apl),
+        ..Default::default()
     };
 
     // Fast example for a subscription with conflate
@@ -36,6 +36,7 @@ pub fn main() -> Result<(), Error> {
         ticker: String::from("BAYN GY Equity"),
         fields: vec!["rt_time_of_trade", "last_price", "bid", "ask"],
         options: None,
+        ..Default::default()
     };
 
     // let options_apl = options!(interval = 5);
@@ -43,6 +44,7 @@ pub fn main() -> Result<(), Error> {
         ticker: String::from("BAS GR Equity"),
         fields: vec!["rt_time_of_trade", "bid"],
         options: None,
+        ..Default::default()
     };
 
     let all_sub = vec![bay_sup, apple_sub];
@@ -67,6 +69,7 @@ pub fn main() -> Result<(), Error> {
         ticker: String::from("BAS GR Equity"),
         fields: vec!["rt_time_of_trade", "last_price", "ask"],
         options: None,
+        ..Default::default()
     }];
 
     session.resubscribe::<Data>(change)?;
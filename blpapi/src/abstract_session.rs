@@ -36,21 +36,38 @@ pub trait AbstractSession: Sized {
         corr_ids: &[CorrelationId],
         request_label: Option<&str>,
     ) -> Result<(), Error> {
-        let session_ptr: *mut blpapi_AbstractSession_t = self.as_abstract_ptr();
-        let corr_id = corr_ids.as_ptr() as *const blpapi_CorrelationId_t;
-        let corr_id_len = corr_ids.len();
-        let (label_ptr, label_len) = if let Some(label) = request_label {
-            (label.as_ptr() as *const c_char, label.len() as c_int)
-        } else {
-            // Null pointer and zero length if no label is provided
-            (std::ptr::null(), 0)
-        };
-        let res = unsafe {
-            blpapi_AbstractSession_cancel(session_ptr, corr_id, corr_id_len, label_ptr, label_len)
-        };
+        let res = cancel_correlation_ids(self.as_abstract_ptr(), corr_ids, request_label);
         if res != 0 {
             return Err(Error::Session);
         }
         Ok(())
     }
 }
+
+/// Cancel the correlation ids on the session, returns the C result code
+///
+/// # Note
+/// `CorrelationId` carries more fields than `blpapi_CorrelationId_t`,
+/// the ids are copied into a C array.
+pub(crate) fn cancel_correlation_ids(
+    session_ptr: *mut blpapi_AbstractSession_t,
+    corr_ids: &[CorrelationId],
+    request_label: Option<&str>,
+) -> c_int {
+    let corr_ids: Vec<blpapi_CorrelationId_t> = corr_ids.iter().map(|cid| cid.id).collect();
+    let (label_ptr, label_len) = if let Some(label) = request_label {
+        (label.as_ptr() as *const c_char, label.len() as c_int)
+    } else {
+        // Null pointer and zero length if no label is provided
+        (std::ptr::null(), 0)
+    };
+    unsafe {
+        blpapi_AbstractSession_cancel(
+            session_ptr,
+            corr_ids.as_ptr(),
+            corr_ids.len(),
+            label_ptr,
+            label_len,
+        )
+    }
+}
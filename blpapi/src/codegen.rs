@@ -0,0 +1,209 @@
+use crate::{data_series::FieldSeries, json::Json, Error};
+use std::{collections::HashSet, fs, path::Path};
+
+/// Max width of generated doc comment lines
+const DOC_WIDTH: usize = 90;
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Rust type of a field, based on the `datatype` and `ftype` of //blp/apiflds
+pub fn rust_type(field: &FieldSeries) -> &'static str {
+    let data_type = field.data_type.as_deref().unwrap_or_default();
+    let field_type = field.field_type.as_deref().unwrap_or_default();
+    if data_type == "Sequence" || field_type == "BulkFormat" {
+        return "blpapi::ref_data::BulkElement";
+    }
+    match (data_type, field_type) {
+        ("Double" | "Float64" | "Float32" | "Float", _) => "Option<f64>",
+        ("Int32" | "Int64" | "Integer", _) => "Option<i64>",
+        ("Bool" | "Boolean", _) => "Option<bool>",
+        ("Date", _) => "Option<chrono::NaiveDate>",
+        ("Datetime", _) => "Option<chrono::NaiveDateTime>",
+        ("", "Real" | "Price" | "Double") => "Option<f64>",
+        ("", "Integer") => "Option<i64>",
+        ("", "Boolean") => "Option<bool>",
+        ("", "Date") => "Option<chrono::NaiveDate>",
+        _ => "Option<String>",
+    }
+}
+
+/// Struct field name of a mnemonic, `None` if the `RefData` derive can not map it back
+pub fn field_ident(mnemonic: &str) -> Option<String> {
+    let valid = mnemonic
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    let ident = mnemonic.to_lowercase();
+    match valid
+        && mnemonic.starts_with(|c: char| !c.is_ascii_digit())
+        && !KEYWORDS.contains(&ident.as_str())
+    {
+        true => Some(ident),
+        false => None,
+    }
+}
+
+/// Struct field name of a mnemonic which is not a valid identifier
+fn fallback_ident(mnemonic: &str) -> String {
+    let ident: String = mnemonic
+        .to_lowercase()
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    match ident.starts_with(|c: char| c.is_ascii_digit()) || KEYWORDS.contains(&ident.as_str()) {
+        true => format!("f_{}", ident),
+        false => ident,
+    }
+}
+
+/// Generator of `RefData` structs from the field information of //blp/apiflds
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefDataGenerator {
+    pub name: String,
+    pub derive: bool,
+}
+
+impl RefDataGenerator {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            derive: true,
+        }
+    }
+
+    /// Use `#[derive(RefData)]`, a manual impl is emitted if a mnemonic is not a valid identifier
+    pub fn derive(mut self, derive: bool) -> Self {
+        self.derive = derive;
+        self
+    }
+
+    /// Generate the struct source, duplicated mnemonics are skipped
+    pub fn generate(&self, fields: &[FieldSeries]) -> String {
+        let mut seen = HashSet::new();
+        let fields: Vec<&FieldSeries> = fields
+            .iter()
+            .filter(|f| !f.mnemonic.is_empty() && seen.insert(f.mnemonic.clone()))
+            .collect();
+        let derive = self.derive && fields.iter().all(|f| field_ident(&f.mnemonic).is_some());
+
+        let mut idents: Vec<String> = vec![];
+        for field in fields.iter() {
+            let mut ident =
+                field_ident(&field.mnemonic).unwrap_or_else(|| fallback_ident(&field.mnemonic));
+            while idents.contains(&ident) {
+                ident.push('_');
+            }
+            idents.push(ident);
+        }
+
+        let mut out = String::new();
+        match derive {
+            true => out.push_str("#[derive(Debug, Default, blpapi::RefData)]\n"),
+            false => out.push_str("#[derive(Debug, Default)]\n"),
+        }
+        out.push_str(&format!("pub struct {} {{\n", self.name));
+        for (field, ident) in fields.iter().zip(idents.iter()) {
+            write_docs(&mut out, field);
+            out.push_str(&format!("    pub {}: {},\n", ident, rust_type(field)));
+        }
+        out.push_str("}\n");
+
+        if !derive {
+            out.push_str(&format!(
+                "\nimpl blpapi::ref_data::RefData for {} {{\n",
+                self.name
+            ));
+            out.push_str("    const FIELDS: &'static [&'static str] = &[\n");
+            for field in fields.iter() {
+                out.push_str(&format!("        {:?},\n", field.mnemonic));
+            }
+            out.push_str("    ];\n\n");
+            out.push_str(
+                "    fn on_field(&mut self, field: &str, element: &blpapi::element::Element) {\n",
+            );
+            out.push_str("        match field {\n");
+            for (field, ident) in fields.iter().zip(idents.iter()) {
+                out.push_str(&format!(
+                    "            {:?} => blpapi::ref_data::RefDataField::set_from_element(&mut self.{}, element),\n",
+                    field.mnemonic, ident
+                ));
+            }
+            out.push_str("            _ => {}\n");
+            out.push_str("        }\n");
+            out.push_str("    }\n");
+            out.push_str("}\n");
+        }
+        out
+    }
+}
+
+/// Doc comment of a field from its description and documentation
+fn write_docs(out: &mut String, field: &FieldSeries) {
+    if !field.desc.is_empty() {
+        out.push_str(&format!("    /// {}\n", field.desc.trim()));
+    }
+    if let Some(documentation) = &field.field_documentation {
+        for paragraph in documentation.lines().filter(|l| !l.trim().is_empty()) {
+            out.push_str("    ///\n");
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                if !line.is_empty() && line.len() + word.len() >= DOC_WIDTH {
+                    out.push_str(&format!("    /// {}\n", line));
+                    line.clear();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+            }
+            if !line.is_empty() {
+                out.push_str(&format!("    /// {}\n", line));
+            }
+        }
+    }
+    out.push_str("    ///\n");
+    out.push_str(&format!(
+        "    /// Mnemonic: `{}`, id: `{}`\n",
+        field.mnemonic, field.id
+    ));
+}
+
+/// JSON of the field information
+pub fn fields_to_json(fields: &[FieldSeries]) -> String {
+    Json::Arr(fields.iter().map(|f| f.to_json()).collect()).to_pretty()
+}
+
+/// Parse field information written by `fields_to_json`
+pub fn fields_from_json(input: &str) -> Result<Vec<FieldSeries>, Error> {
+    let json = Json::parse(input)?;
+    let values = json.as_array().ok_or_else(|| {
+        Error::struct_error("codegen", "fields_from_json", "Expected an array of fields")
+    })?;
+    values
+        .iter()
+        .map(|value| {
+            FieldSeries::from_json(value).ok_or_else(|| {
+                Error::struct_error("codegen", "fields_from_json", "Field without mnemonic")
+            })
+        })
+        .collect()
+}
+
+/// Write the field information into a JSON file
+pub fn write_field_dump<P: AsRef<Path>>(path: P, fields: &[FieldSeries]) -> Result<(), Error> {
+    fs::write(path, fields_to_json(fields))?;
+    Ok(())
+}
+
+/// Read field information from a JSON file written by `write_field_dump`
+pub fn read_field_dump<P: AsRef<Path>>(path: P) -> Result<Vec<FieldSeries>, Error> {
+    fields_from_json(&fs::read_to_string(path)?)
+}
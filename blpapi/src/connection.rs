@@ -0,0 +1,219 @@
+use crate::{
+    event::{Event, EventType},
+    message::Message,
+    names::{SESSION_CONNECTION_DOWN, SESSION_CONNECTION_UP, SESSION_TERMINATED},
+    session_options::ServerAddress,
+};
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    time::SystemTime,
+};
+
+/// State of the connection of a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    /// No `SessionConnectionUp` received yet
+    #[default]
+    Connecting,
+    Up,
+    /// Connection lost, the SDK tries the next server address
+    Down,
+    Terminated,
+}
+
+/// Server of a connection, as reported by `SessionConnectionUp`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectedServer {
+    pub host: String,
+    pub port: u16,
+    /// Index of the configured server address, `None` if the server is not configured
+    pub index: Option<usize>,
+}
+
+/// Change of the connection, sent to the receivers of `ConnectionMonitor::events`
+#[derive(Debug, Clone)]
+pub enum ConnectionEvent {
+    Up(ConnectedServer),
+    Down(Option<ConnectedServer>),
+    /// Connection re-established on another server address
+    Failover {
+        from: ConnectedServer,
+        to: ConnectedServer,
+    },
+    Terminated,
+}
+
+/// Snapshot of the connection of a session
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStatus {
+    pub state: ConnectionState,
+    /// Server address of the last `SessionConnectionUp`
+    pub server: Option<ConnectedServer>,
+    pub since: Option<SystemTime>,
+    pub failovers: u64,
+}
+
+impl ConnectionStatus {
+    pub fn is_up(&self) -> bool {
+        self.state == ConnectionState::Up
+    }
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    status: ConnectionStatus,
+    servers: Vec<ServerAddress>,
+    listeners: Vec<mpsc::Sender<ConnectionEvent>>,
+}
+
+/// Connection state of a session, updated from its `SessionStatus` events
+///
+/// # Note
+/// Clones share the state, e.g. to report it from a health check thread.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionMonitor {
+    state: Arc<Mutex<MonitorState>>,
+}
+
+impl ConnectionMonitor {
+    /// Monitor of the configured server addresses, used to resolve the index of a server
+    pub fn new(servers: Vec<ServerAddress>) -> Self {
+        let state = MonitorState {
+            servers,
+            ..Default::default()
+        };
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Current connection status
+    pub fn status(&self) -> ConnectionStatus {
+        self.state.lock().unwrap().status.clone()
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state.lock().unwrap().status.state
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.state() == ConnectionState::Up
+    }
+
+    /// Server address the session is connected to
+    pub fn server(&self) -> Option<ConnectedServer> {
+        let state = self.state.lock().unwrap();
+        match state.status.state {
+            ConnectionState::Up => state.status.server.clone(),
+            _ => None,
+        }
+    }
+
+    /// Receiver of all following connection changes
+    pub fn events(&self) -> mpsc::Receiver<ConnectionEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.state.lock().unwrap().listeners.push(tx);
+        rx
+    }
+
+    /// Update the state from the messages of a `SessionStatus` event
+    pub fn process_event(&self, event: &Event) -> Vec<ConnectionEvent> {
+        if event.event_type != EventType::SessionStatus {
+            return vec![];
+        }
+        event
+            .messages()
+            .filter_map(|message| self.process_message(&message))
+            .collect()
+    }
+
+    /// Update the state from a `SessionStatus` message, returns the failover if any
+    pub fn process_message(&self, message: &Message) -> Option<ConnectionEvent> {
+        let m_type = message.message_type();
+        if m_type == *SESSION_CONNECTION_UP {
+            let server = message
+                .element()
+                .element_value::<String>("server")
+                .unwrap_or_default();
+            self.connection_up(&server)
+        } else {
+            if m_type == *SESSION_CONNECTION_DOWN {
+                self.connection_down();
+            } else if m_type == *SESSION_TERMINATED {
+                self.update(ConnectionState::Terminated, ConnectionEvent::Terminated);
+            }
+            None
+        }
+    }
+
+    /// Mark the connection as up on the server, given as `host:port`, returns the failover if any
+    pub fn connection_up(&self, server: &str) -> Option<ConnectionEvent> {
+        let server = self.resolve(server);
+        let from = {
+            let state = self.state.lock().unwrap();
+            state.status.server.clone()
+        };
+        let failover = match from {
+            Some(from) if from.host != server.host || from.port != server.port => {
+                Some(ConnectionEvent::Failover {
+                    from,
+                    to: server.clone(),
+                })
+            }
+            _ => None,
+        };
+        {
+            let mut state = self.state.lock().unwrap();
+            state.status.server = Some(server.clone());
+            if failover.is_some() {
+                state.status.failovers += 1;
+            }
+        }
+        self.update(ConnectionState::Up, ConnectionEvent::Up(server));
+        if let Some(failover) = &failover {
+            self.notify(failover.clone());
+        }
+        failover
+    }
+
+    /// Mark the connection as down
+    pub fn connection_down(&self) {
+        let server = self.state.lock().unwrap().status.server.clone();
+        self.update(ConnectionState::Down, ConnectionEvent::Down(server))
+    }
+
+    fn update(&self, new_state: ConnectionState, event: ConnectionEvent) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.status.state = new_state;
+            state.status.since = Some(SystemTime::now());
+        }
+        self.notify(event);
+    }
+
+    fn notify(&self, event: ConnectionEvent) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .listeners
+            .retain(|listener| listener.send(event.clone()).is_ok());
+    }
+
+    /// Server of `host:port` with the index of its configured address, if any
+    fn resolve(&self, server: &str) -> ConnectedServer {
+        let (host, port) = match server.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().unwrap_or_default()),
+            None => (server, 0),
+        };
+        let state = self.state.lock().unwrap();
+        let index = state
+            .servers
+            .iter()
+            .find(|s| s.host.eq_ignore_ascii_case(host) && s.port == port)
+            .map(|s| s.index);
+        ConnectedServer {
+            host: host.to_string(),
+            port,
+            index,
+        }
+    }
+}
@@ -3,7 +3,8 @@ use blpapi_sys::{
     blpapi_ConstantList_getConstantAt, blpapi_ConstantList_name, blpapi_ConstantList_numConstants,
     blpapi_ConstantList_setUserData, blpapi_ConstantList_status, blpapi_ConstantList_t,
     blpapi_ConstantList_userData, blpapi_Constant_datatype, blpapi_Constant_description,
-    blpapi_Constant_getValueAsChar, blpapi_Constant_t, blpapi_DataType_t_BLPAPI_DATATYPE_BOOL,
+    blpapi_Constant_getValueAsChar, blpapi_Constant_getValueAsString, blpapi_Constant_name,
+    blpapi_Constant_t, blpapi_DataType_t_BLPAPI_DATATYPE_BOOL,
     blpapi_DataType_t_BLPAPI_DATATYPE_BYTE, blpapi_DataType_t_BLPAPI_DATATYPE_BYTEARRAY,
     blpapi_DataType_t_BLPAPI_DATATYPE_CHAR, blpapi_DataType_t_BLPAPI_DATATYPE_CHOICE,
     blpapi_DataType_t_BLPAPI_DATATYPE_CORRELATION_ID, blpapi_DataType_t_BLPAPI_DATATYPE_DATE,
@@ -76,6 +77,32 @@ impl From<OsInt> for DataType {
         }
     }
 }
+/// Data type names of the schema and of //blp/apiflds, e.g. `Double` or `Int64`
+impl From<&str> for DataType {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "Bool" | "Boolean" => DataType::BlpBool,
+            "Char" | "Character" => DataType::BlpChar,
+            "Byte" => DataType::BlpByte,
+            "Int32" => DataType::BlpInt32,
+            "Int64" | "Integer" => DataType::BlpInt64,
+            "Float32" | "Float" => DataType::BlpFloat32,
+            "Float64" | "Double" => DataType::BlpFloat64,
+            "String" => DataType::BlpString,
+            "Byte Array" | "ByteArray" => DataType::BlpByteArray,
+            "Date" => DataType::BlpDate,
+            "Datetime" => DataType::BlpDatetime,
+            "Time" => DataType::BlpTime,
+            "Decimal" => DataType::BlpDecimal,
+            "Enumeration" => DataType::BlpEnumeration,
+            "Sequence" => DataType::BlpSequence,
+            "Choice" => DataType::BlpChoice,
+            "Correlation ID" => DataType::BlpCorrelationId,
+            _ => DataType::Unknown,
+        }
+    }
+}
+
 impl Display for DataType {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         match self {
@@ -281,6 +308,31 @@ impl Constant {
         }
     }
 
+    /// Get the name of the constant
+    pub fn name(&self) -> Name {
+        let name = NameBuilder::default();
+        if self.ptr.is_null() {
+            return name.build();
+        }
+        let name_ptr = unsafe { blpapi_Constant_name(self.ptr) };
+        name.by_ptr(name_ptr).build()
+    }
+
+    /// Get the value of the constant as string
+    pub fn value_as_string(&self) -> Result<String, Error> {
+        if self.ptr.is_null() {
+            return Err(Error::Constant);
+        }
+        let mut buffer: *const std::ffi::c_char = null();
+        let res = unsafe { blpapi_Constant_getValueAsString(self.ptr, &mut buffer) };
+        Error::check(res)?;
+        if buffer.is_null() {
+            return Err(Error::Constant);
+        }
+        let value = unsafe { CStr::from_ptr(buffer) };
+        Ok(value.to_string_lossy().into_owned())
+    }
+
     pub fn description(self) {
         let _data_type = unsafe {
             let constant: *const blpapi_Constant_t = self.ptr;
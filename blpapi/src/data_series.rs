@@ -1,5 +1,7 @@
 use std::{collections::HashMap, convert::TryFrom};
 
+use crate::json::Json;
+
 use crate::core::{
     BLPAPI_DEFAULT_ALL, BLPAPI_DEFAULT_REALTIME, BLPAPI_DEFAULT_STATIC,
     BLPAPI_LNG_OVERRIDE_CHINESE_SIMP, BLPAPI_LNG_OVERRIDE_CHINESE_TRAD,
@@ -80,7 +82,7 @@ impl From<FieldTypes> for &str {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct FieldSeries {
     pub id: String,
     pub mnemonic: String,
@@ -96,6 +98,75 @@ pub struct FieldSeries {
     pub overrides: Vec<String>,
 }
 
+impl FieldSeries {
+    pub(crate) fn to_json(&self) -> Json {
+        let map = |hm: &HashMap<String, String>| {
+            let mut fields: Vec<(String, Json)> = hm
+                .iter()
+                .map(|(k, v)| (k.clone(), Json::Str(v.clone())))
+                .collect();
+            fields.sort_by(|a, b| a.0.cmp(&b.0));
+            Json::Obj(fields)
+        };
+        Json::object(vec![
+            ("id", Json::Str(self.id.clone())),
+            ("mnemonic", Json::Str(self.mnemonic.clone())),
+            ("desc", Json::Str(self.desc.clone())),
+            ("dataType", Json::opt_str(&self.data_type)),
+            ("fieldType", Json::opt_str(&self.field_type)),
+            ("fieldCategory", Json::opt_str(&self.field_category)),
+            (
+                "fieldDocumentation",
+                Json::opt_str(&self.field_documentation),
+            ),
+            ("fieldProperty", map(&self.field_property)),
+            (
+                "fieldDefaultFormatting",
+                map(&self.field_default_formatting),
+            ),
+            ("fieldError", map(&self.field_error)),
+            ("other", map(&self.other)),
+            (
+                "overrides",
+                Json::Arr(self.overrides.iter().cloned().map(Json::Str).collect()),
+            ),
+        ])
+    }
+
+    pub(crate) fn from_json(json: &Json) -> Option<Self> {
+        let string = |key: &str| json.get(key).and_then(|v| v.as_str()).map(String::from);
+        let map = |key: &str| -> HashMap<String, String> {
+            json.get(key)
+                .and_then(|v| v.as_object())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        };
+        let overrides = json
+            .get("overrides")
+            .and_then(|v| v.as_array())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+        Some(FieldSeries {
+            id: string("id").unwrap_or_default(),
+            mnemonic: string("mnemonic")?,
+            desc: string("desc").unwrap_or_default(),
+            data_type: string("dataType"),
+            field_type: string("fieldType"),
+            field_category: string("fieldCategory"),
+            field_documentation: string("fieldDocumentation"),
+            field_property: map("fieldProperty"),
+            field_default_formatting: map("fieldDefaultFormatting"),
+            field_error: map("fieldError"),
+            other: map("other"),
+            overrides,
+        })
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct FieldSeriesBuilder {
     pub id: String,
@@ -1,6 +1,143 @@
-use crate::element::Element;
+use crate::{element::Element, validation::ValidationError};
+use blpapi_sys::*;
 use regex::Error as RegexErr;
-use std::{num::ParseIntError, string::ParseError};
+use std::{ffi::CStr, io, num::ParseIntError, string::ParseError};
+
+/// Class of a blpapi error code, the upper bits of the code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorClass {
+    #[default]
+    Unknown,
+    InvalidState,
+    InvalidArgument,
+    Io,
+    Conversion,
+    Bounds,
+    NotFound,
+    FieldNotFound,
+    Unsupported,
+}
+
+impl From<i32> for ErrorClass {
+    fn from(code: i32) -> Self {
+        match code as u32 & 0xff0000 {
+            BLPAPI_INVALIDSTATE_CLASS => ErrorClass::InvalidState,
+            BLPAPI_INVALIDARG_CLASS => ErrorClass::InvalidArgument,
+            BLPAPI_IOERROR_CLASS => ErrorClass::Io,
+            BLPAPI_CNVERROR_CLASS => ErrorClass::Conversion,
+            BLPAPI_BOUNDSERROR_CLASS => ErrorClass::Bounds,
+            BLPAPI_NOTFOUND_CLASS => ErrorClass::NotFound,
+            BLPAPI_FLDNOTFOUND_CLASS => ErrorClass::FieldNotFound,
+            BLPAPI_UNSUPPORTED_CLASS => ErrorClass::Unsupported,
+            _ => ErrorClass::Unknown,
+        }
+    }
+}
+
+/// Kind of a blpapi error code, one per `BLPAPI_ERROR_*` constant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlpErrorKind {
+    #[default]
+    Unknown,
+    IllegalArg,
+    IllegalAccess,
+    InvalidSession,
+    DuplicateCorrelationId,
+    InternalError,
+    ResolveFailed,
+    ConnectFailed,
+    IllegalState,
+    CodecFailure,
+    IndexOutOfRange,
+    InvalidConversion,
+    ItemNotFound,
+    IoError,
+    CorrelationNotFound,
+    ServiceNotFound,
+    LogonLookupFailed,
+    DsLookupFailed,
+    UnsupportedOperation,
+    DsPropertyNotFound,
+    MsgTooLarge,
+    /// Code without a `BLPAPI_ERROR_*` constant
+    Other,
+}
+
+impl From<i32> for BlpErrorKind {
+    fn from(code: i32) -> Self {
+        match code as u32 {
+            BLPAPI_ERROR_UNKNOWN => BlpErrorKind::Unknown,
+            BLPAPI_ERROR_ILLEGAL_ARG => BlpErrorKind::IllegalArg,
+            BLPAPI_ERROR_ILLEGAL_ACCESS => BlpErrorKind::IllegalAccess,
+            BLPAPI_ERROR_INVALID_SESSION => BlpErrorKind::InvalidSession,
+            BLPAPI_ERROR_DUPLICATE_CORRELATIONID => BlpErrorKind::DuplicateCorrelationId,
+            BLPAPI_ERROR_INTERNAL_ERROR => BlpErrorKind::InternalError,
+            BLPAPI_ERROR_RESOLVE_FAILED => BlpErrorKind::ResolveFailed,
+            BLPAPI_ERROR_CONNECT_FAILED => BlpErrorKind::ConnectFailed,
+            BLPAPI_ERROR_ILLEGAL_STATE => BlpErrorKind::IllegalState,
+            BLPAPI_ERROR_CODEC_FAILURE => BlpErrorKind::CodecFailure,
+            BLPAPI_ERROR_INDEX_OUT_OF_RANGE => BlpErrorKind::IndexOutOfRange,
+            BLPAPI_ERROR_INVALID_CONVERSION => BlpErrorKind::InvalidConversion,
+            BLPAPI_ERROR_ITEM_NOT_FOUND => BlpErrorKind::ItemNotFound,
+            BLPAPI_ERROR_IO_ERROR => BlpErrorKind::IoError,
+            BLPAPI_ERROR_CORRELATION_NOT_FOUND => BlpErrorKind::CorrelationNotFound,
+            BLPAPI_ERROR_SERVICE_NOT_FOUND => BlpErrorKind::ServiceNotFound,
+            BLPAPI_ERROR_LOGON_LOOKUP_FAILED => BlpErrorKind::LogonLookupFailed,
+            BLPAPI_ERROR_DS_LOOKUP_FAILED => BlpErrorKind::DsLookupFailed,
+            BLPAPI_ERROR_UNSUPPORTED_OPERATION => BlpErrorKind::UnsupportedOperation,
+            BLPAPI_ERROR_DS_PROPERTY_NOT_FOUND => BlpErrorKind::DsPropertyNotFound,
+            BLPAPI_ERROR_MSG_TOO_LARGE => BlpErrorKind::MsgTooLarge,
+            _ => BlpErrorKind::Other,
+        }
+    }
+}
+
+/// Error code returned by the blpapi C library
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlpError {
+    pub code: i32,
+    pub kind: BlpErrorKind,
+    pub class: ErrorClass,
+    /// Text of `blpapi_getLastErrorDescription`
+    pub message: String,
+}
+
+impl BlpError {
+    /// Error of the code without description
+    pub fn new(code: i32) -> Self {
+        Self {
+            code,
+            kind: code.into(),
+            class: code.into(),
+            message: String::new(),
+        }
+    }
+
+    /// Error of the code with the description of the last error of the calling thread
+    pub fn from_code(code: i32) -> Self {
+        let desc = unsafe { blpapi_getLastErrorDescription(code) };
+        let message = match desc.is_null() {
+            true => String::new(),
+            false => unsafe { CStr::from_ptr(desc).to_string_lossy().into_owned() },
+        };
+        Self {
+            message,
+            ..Self::new(code)
+        }
+    }
+}
+
+impl std::fmt::Display for BlpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{:?} ({:?}, code {})", self.kind, self.class, self.code)?;
+        if !self.message.is_empty() {
+            write!(f, ": {}", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BlpError {}
 
 /// Error converted from `c_int`
 #[derive(Debug)]
@@ -17,7 +154,10 @@ pub enum Error {
     ExpiredAuthToken,
     TokenInUse,
     /// Generic blpapi error return
+    #[deprecated(note = "Error codes of the C library are returned as `Error::Blp`")]
     Generic(i32),
+    /// Error code of the blpapi C library
+    Blp(BlpError),
     /// Some element were not found
     NotFound(String),
     /// Regex Error
@@ -26,6 +166,8 @@ pub enum Error {
     ParseError(ParseError),
     /// Usize Parse Error
     ParseIntError(ParseIntError),
+    /// Io Error
+    Io(io::Error),
     /// Constant List Error
     ConstantList,
     /// Constant Error
@@ -37,6 +179,14 @@ pub enum Error {
         sub_category: Option<String>,
         message: String,
     },
+    /// A request failed, from a responseError element or a RequestFailure message
+    RequestFailure {
+        source: String,
+        code: i32,
+        category: String,
+        sub_category: Option<String>,
+        message: String,
+    },
     /// A fieldError element was found
     Field {
         id: String,
@@ -65,6 +215,13 @@ pub enum Error {
     SubscriptionStatus,
     /// Timeout event
     TimeOut,
+    /// Invalid value of a configuration key
+    Config {
+        key: String,
+        msg: String,
+    },
+    /// Request values violate the service schema
+    Validation(Vec<ValidationError>),
 }
 
 impl std::fmt::Display for Error {
@@ -73,6 +230,16 @@ impl std::fmt::Display for Error {
             Error::ParseError(msg) => write!(f, "Parse Error: {:?}", msg),
             Error::ParseIntError(msg) => write!(f, "Parse Int Error: {:?}", msg),
             Error::RegexErr(msg) => write!(f, "Regex Error: {:?}", msg),
+            Error::Io(e) => write!(f, "Io Error: {}", e),
+            Error::Blp(e) => write!(f, "Blp Error: {}", e),
+            Error::Config { key, msg } => write!(f, "Config Error: {}: {}", key, msg),
+            Error::RequestFailure {
+                category, message, ..
+            } => write!(f, "Request Failure: {} ({})", message, category),
+            Error::Validation(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "Validation Error: {}", errors.join("; "))
+            }
             _ => write!(f, "{:?}", self),
         }
     }
@@ -84,6 +251,8 @@ impl std::error::Error for Error {
             Error::ParseError(e) => Some(e),
             Error::ParseIntError(e) => Some(e),
             Error::RegexErr(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Blp(e) => Some(e),
             _ => None,
         }
     }
@@ -101,6 +270,18 @@ impl From<ParseIntError> for Error {
     }
 }
 
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<BlpError> for Error {
+    fn from(e: BlpError) -> Self {
+        Error::Blp(e)
+    }
+}
+
 impl From<RegexErr> for Error {
     fn from(e: RegexErr) -> Self {
         Error::RegexErr(e)
@@ -109,6 +290,7 @@ impl From<RegexErr> for Error {
 
 impl Error {
     /// Check if response is an error(!=0)
+    /// Codes 100 to 119 are errors of this crate, others are `Error::Blp`
     pub fn check(res: i32) -> Result<(), Error> {
         if res == 0 {
             Ok(())
@@ -137,10 +319,7 @@ impl Error {
                 117 => Err(Error::Schema),
                 118 => Err(Error::Service),
                 119 => Err(Error::SchemaType),
-                _ => {
-                    log::debug!("Unrecognized error code: {}", res);
-                    Err(Error::Generic(res))
-                }
+                _ => Err(Error::Blp(BlpError::from_code(res))),
             }
         }
     }
@@ -164,6 +343,26 @@ impl Error {
         }
     }
 
+    /// Create a request failure from a responseError or reason element
+    pub(crate) fn request_failure(element: Element) -> Error {
+        let string = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| element.get_element(name).and_then(|e| e.get_at(0)))
+        };
+        let code = ["code", "errorCode"]
+            .iter()
+            .find_map(|name| element.get_element(name).and_then(|e| e.get_at(0)))
+            .unwrap_or_default();
+        Error::RequestFailure {
+            source: string(&["source"]).unwrap_or_default(),
+            code,
+            category: string(&["category"]).unwrap_or_default(),
+            sub_category: string(&["subcategory"]),
+            message: string(&["message", "description"]).unwrap_or_default(),
+        }
+    }
+
     /// Create a field error
     pub(crate) fn field(element: Element) -> Error {
         let id = element
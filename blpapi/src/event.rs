@@ -1,22 +1,31 @@
 use crate::{
+    abstract_session::AbstractSession,
+    connection::{ConnectedServer, ConnectionEvent, ConnectionMonitor},
     correlation_id::CorrelationId,
     data_series::{DataSeries, DataSeriesBuilder},
-    message::Message,
+    element::Element,
+    message::{Message, MessageTypeCore},
     message_iterator::MessageIterator,
     names::{
-        SERVICE_DOWN, SERVICE_OPEN_FAILURE, SERVICE_REGISTER_FAILURE, SESSION_STARTUP_FAILURE,
-        SESSION_TERMINATED, SUBSCRIPTION_FAILURE, SUBSCRIPTION_TERMINATED,
+        DATA_LOSS, REASON, RESPONSE_ERROR, SERVICE_DOWN, SERVICE_OPEN_FAILURE,
+        SERVICE_REGISTER_FAILURE, SESSION_CONNECTION_DOWN, SESSION_CONNECTION_UP,
+        SESSION_STARTUP_FAILURE, SESSION_TERMINATED, SLOW_CONSUMER_WARNING,
+        SLOW_CONSUMER_WARNING_CLEARED, SUBSCRIPTION_FAILURE, SUBSCRIPTION_STARTED,
+        SUBSCRIPTION_STREAMS_ACTIVATED, SUBSCRIPTION_STREAMS_DEACTIVATED, SUBSCRIPTION_TERMINATED,
     },
+    recording::{EventRecorder, EventReplay},
+    request_control::RequestControl,
     session::{Session, SubscriptionStatus},
-    subscription_list::SubscriptionRegistry,
+    subscription_list::{StreamState, SubscriptionReason, SubscriptionRegistry, TickerInfo},
     Error, RefData,
 };
 use blpapi_sys::*;
 use std::{
     collections::{HashSet, VecDeque},
-    marker::PhantomData,
     os::raw::c_int,
     ptr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 /// Event Builder
@@ -140,6 +149,29 @@ impl From<c_int> for EventType {
     }
 }
 
+impl From<EventType> for c_int {
+    fn from(v: EventType) -> Self {
+        let event_type = match v {
+            EventType::Admin => BLPAPI_EVENTTYPE_ADMIN,
+            EventType::SessionStatus => BLPAPI_EVENTTYPE_SESSION_STATUS,
+            EventType::SubscriptionStatus => BLPAPI_EVENTTYPE_SUBSCRIPTION_STATUS,
+            EventType::RequestStatus => BLPAPI_EVENTTYPE_REQUEST_STATUS,
+            EventType::Response => BLPAPI_EVENTTYPE_RESPONSE,
+            EventType::PartialResponse => BLPAPI_EVENTTYPE_PARTIAL_RESPONSE,
+            EventType::SubscriptionData => BLPAPI_EVENTTYPE_SUBSCRIPTION_DATA,
+            EventType::ServiceStatus => BLPAPI_EVENTTYPE_SERVICE_STATUS,
+            EventType::Timeout => BLPAPI_EVENTTYPE_TIMEOUT,
+            EventType::AuthorizationStatus => BLPAPI_EVENTTYPE_AUTHORIZATION_STATUS,
+            EventType::ResolutionStatus => BLPAPI_EVENTTYPE_RESOLUTION_STATUS,
+            EventType::TopicStatus => BLPAPI_EVENTTYPE_TOPIC_STATUS,
+            EventType::TokenStatus => BLPAPI_EVENTTYPE_TOKEN_STATUS,
+            EventType::Request => BLPAPI_EVENTTYPE_REQUEST,
+            EventType::Unknown => return -1,
+        };
+        event_type as c_int
+    }
+}
+
 impl Drop for Event {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
@@ -154,6 +186,9 @@ pub struct SessionEvents<'a> {
     exit: bool,
     correlation_id: CorrelationId,
     event_queue: EventQueue,
+    control: RequestControl,
+    /// Arrival of the last event, the session timeout applies from then on
+    last_event: Instant,
 }
 
 impl<'a> SessionEvents<'a> {
@@ -167,18 +202,52 @@ impl<'a> SessionEvents<'a> {
             correlation_id,
             exit: false,
             event_queue,
+            control: RequestControl::default(),
+            last_event: Instant::now(),
         }
     }
+
+    /// Stop waiting for the request once the deadline passed or the token is cancelled
+    ///
+    /// # Note
+    /// The session timeout still applies while no event arrives.
+    pub fn control(mut self, control: RequestControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Session of the request, e.g. to send the next request once this one completed
+    pub(crate) fn into_session(self) -> &'a mut Session {
+        self.session
+    }
+
+    /// Cancel the request, the data received so far stays with the caller
+    fn stop(&mut self) -> Result<Option<Event>, Error> {
+        self.exit = true;
+        if self.session.replay.is_none() {
+            if let Err(e) = self.session.cancel(&[self.correlation_id], None) {
+                log::warn!("Cancelling request failed: {}", e);
+            }
+        }
+        Err(Error::TimeOut)
+    }
+
     fn try_next(&mut self) -> Result<Option<Event>, Error> {
         if self.exit {
             return Ok(None);
         }
         loop {
-            let event = match self.session.event_queue {
-                true => self.event_queue.next_event()?,
-                false => self.session.next_event()?,
-            };
+            if self.control.is_stopped() {
+                return self.stop();
+            }
+            let time_out = self.control.wait_ms(self.session.time_out);
+            let event = self
+                .session
+                .next_request_event(&mut self.event_queue, time_out)?;
             let event_type = event.event_type;
+            if event_type != EventType::Timeout {
+                self.last_event = Instant::now();
+            }
             match event_type {
                 EventType::SessionStatus => {
                     if event
@@ -198,11 +267,33 @@ impl<'a> SessionEvents<'a> {
                         return Ok(None);
                     }
                 }
-                EventType::PartialResponse => return Ok(Some(event)),
+                EventType::RequestStatus => {
+                    if let Some(error) = event.messages().find_map(|m| request_failure(&m)) {
+                        self.exit = true;
+                        return Err(error);
+                    }
+                }
+                EventType::PartialResponse => {
+                    if let Some(error) = event.messages().find_map(|m| request_failure(&m)) {
+                        self.exit = true;
+                        return Err(error);
+                    }
+                    return Ok(Some(event));
+                }
                 EventType::Response => {
                     self.exit = true;
+                    if let Some(error) = event.messages().find_map(|m| request_failure(&m)) {
+                        return Err(error);
+                    }
                     return Ok(Some(event));
                 }
+                // Waiting in slices until the deadline or the cancellation
+                EventType::Timeout if !self.control.is_unbounded() => {
+                    let idle = Duration::from_millis(self.session.time_out as u64);
+                    if self.session.time_out > 0 && self.last_event.elapsed() >= idle {
+                        return Err(Error::TimeOut);
+                    }
+                }
                 EventType::Timeout => return Err(Error::TimeOut),
                 _ => (),
             }
@@ -210,6 +301,19 @@ impl<'a> SessionEvents<'a> {
     }
 }
 
+/// Failure of a `RequestFailure` message or a message with a `responseError` element
+fn request_failure(message: &Message) -> Option<Error> {
+    let element = message.element();
+    match message.message_type.message_type {
+        MessageTypeCore::RequestFailure => Some(Error::request_failure(
+            element.get_named_element(&REASON).unwrap_or(element),
+        )),
+        _ => element
+            .get_named_element(&RESPONSE_ERROR)
+            .map(Error::request_failure),
+    }
+}
+
 impl<'a> Iterator for SessionEvents<'a> {
     type Item = Result<Event, Error>;
     fn next(&mut self) -> Option<Result<Event, Error>> {
@@ -371,109 +475,410 @@ pub enum SubscriptionMsg<R> {
     StatusChange {
         ticker: String,
         status: SubscriptionStatus,
+        reason: Option<SubscriptionReason>,
+        streams: Vec<StreamState>,
+    },
+    /// Connection to the server lost, the session might recover on its own
+    ConnectionDown,
+    /// Connection to the server (re-)established
+    ConnectionUp,
+    /// Connection re-established on another server address, follows `ConnectionUp`
+    Failover {
+        from: ConnectedServer,
+        to: ConnectedServer,
+    },
+    /// Supervisor is about to restart the session after the given delay
+    Reconnecting {
+        attempt: u32,
+        delay: Duration,
+    },
+    /// Session restarted and subscriptions replayed
+    Reconnected {
+        attempt: u32,
+        resubscribed: usize,
+    },
+    /// Event queue of the session reached the high water mark
+    SlowConsumerWarning,
+    /// Event queue of the session is back below the low water mark
+    SlowConsumerWarningCleared,
+    /// Messages dropped by the SDK, with the cumulative count of each affected ticker
+    DataLoss {
+        dropped: u64,
+        total: u64,
+        tickers: Vec<(String, u64)>,
     },
     Terminated,
 }
 
+/// Policy for subscription data while the consumer is flagged as slow
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SlowConsumerPolicy {
+    /// Deliver every update
+    #[default]
+    Deliver,
+    /// Merge all pending updates of a ticker into a single message
+    Conflate,
+    /// Keep only the last pending update of a ticker
+    LastValue,
+}
+
+/// Decoder turning a subscription data message of a ticker into a typed value
+pub type SubscriptionDecoder<R> = Box<dyn FnMut(&Message, &TickerInfo) -> Option<R> + Send>;
+
+/// Merges the requested fields of a message into a pending value
+type SubscriptionMerge<R> = fn(&mut R, &Message, &HashSet<String>);
+
 // SubscriptionStream
 pub struct SubscriptionStream<R> {
     session_ptr: *mut blpapi_Session_t,
     registry: SubscriptionRegistry,
     subscription_status: SubscriptionStatus,
-    message_buffer: VecDeque<SubscriptionMsg<R>>,
+    /// Buffered messages with the correlation id of their subscription
+    message_buffer: VecDeque<(Option<u64>, SubscriptionMsg<R>)>,
+    decoder: SubscriptionDecoder<R>,
+    merge: Option<SubscriptionMerge<R>>,
+    policy: SlowConsumerPolicy,
+    slow_consumer: bool,
+    data_loss: u64,
+    recorder: Option<EventRecorder>,
+    replay: Option<Arc<Mutex<EventReplay>>>,
+    connection: ConnectionMonitor,
     exit: bool,
-    _marker: PhantomData<R>,
 }
 
 unsafe impl<R> Send for SubscriptionStream<R> {}
 
 impl<R> SubscriptionStream<R>
 where
-    R: RefData,
+    R: RefData + 'static,
 {
     pub fn new(ptr: *mut blpapi_Session_t, registry: SubscriptionRegistry) -> Self {
+        let mut stream = Self::with_decoder(ptr, registry, Box::new(decode_ref_data::<R>));
+        stream.merge = Some(merge_ref_data::<R>);
+        stream
+    }
+}
+
+/// Decode the requested fields of a message into `RefData`
+fn decode_ref_data<R: RefData>(message: &Message, info: &TickerInfo) -> Option<R> {
+    let ele = message.element();
+    let len = ele.num_elements();
+    let mut data_builder: DataSeriesBuilder<R> =
+        DataSeriesBuilder::with_capacity(len, info.ticker.clone());
+    for field in ele.elements() {
+        let mut value = R::default();
+        let name = field.string_name();
+        if info.requested_fields.contains(&name) {
+            value.on_field(&field.string_name(), &field);
+            data_builder.values.push(value);
+        }
+    }
+    let mut data_rows = data_builder.to_rows();
+    if !data_rows.is_empty() {
+        let f_item = data_rows.remove(0);
+        Some(f_item.data)
+    } else {
+        None
+    }
+}
+
+/// Merge the requested fields of a message into pending `RefData`
+fn merge_ref_data<R: RefData>(data: &mut R, message: &Message, requested_fields: &HashSet<String>) {
+    for field in message.element().elements() {
+        let name = field.string_name();
+        if requested_fields.contains(&name) {
+            data.on_field(&name, &field);
+        }
+    }
+}
+
+impl<R> SubscriptionStream<R> {
+    /// Create a stream decoding the data messages with the given decoder
+    pub fn with_decoder(
+        ptr: *mut blpapi_Session_t,
+        registry: SubscriptionRegistry,
+        decoder: SubscriptionDecoder<R>,
+    ) -> Self {
         let subscription_status = SubscriptionStatus::Subscribing;
-        let vec_d: VecDeque<SubscriptionMsg<R>> = VecDeque::new();
+        let vec_d: VecDeque<(Option<u64>, SubscriptionMsg<R>)> = VecDeque::new();
         SubscriptionStream {
             session_ptr: ptr,
             registry,
             subscription_status,
             message_buffer: vec_d,
+            decoder,
+            merge: None,
+            policy: SlowConsumerPolicy::default(),
+            slow_consumer: false,
+            data_loss: 0,
+            recorder: None,
+            replay: None,
+            connection: ConnectionMonitor::default(),
             exit: false,
-            _marker: PhantomData,
         }
     }
 
-    fn process_subscription_message(
-        &self,
-        message: &Message,
-        ticker: String,
-        requested_fields: &HashSet<String>,
-    ) -> Option<DataSeries<R>> {
-        let ele = message.element();
-        let len = ele.num_elements();
-        let mut data_builder: DataSeriesBuilder<R> = DataSeriesBuilder::with_capacity(len, ticker);
-        for field in ele.elements() {
-            let mut value = R::default();
-            let name = field.string_name();
-            if requested_fields.contains(&name) {
-                value.on_field(&field.string_name(), &field);
-                data_builder.values.push(value);
+    /// Setting the slow consumer policy
+    pub fn policy(mut self, policy: SlowConsumerPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Record every event the stream receives
+    pub fn recorder(mut self, recorder: Option<EventRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    /// Read the events from a replay instead of the session
+    pub fn replay(mut self, replay: Option<Arc<Mutex<EventReplay>>>) -> Self {
+        self.replay = replay;
+        self
+    }
+
+    /// Connection monitor updated from the session status messages
+    pub fn connection(mut self, connection: ConnectionMonitor) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// Next event of the session or the replay, `None` once the replay is exhausted
+    fn next_event(&mut self) -> Option<Result<Event, Error>> {
+        if let Some(replay) = &self.replay {
+            return replay.lock().unwrap().next_event();
+        }
+        let mut event_ptr = std::ptr::null_mut();
+        let res = unsafe { blpapi_Session_nextEvent(self.session_ptr, &mut event_ptr, 0) };
+        if res != 0 {
+            return Some(Err(Error::InternalError));
+        }
+        let event = EventBuilder::default().ptr(event_ptr).build();
+        if let Some(recorder) = &self.recorder {
+            if let Err(e) = recorder.record(&event) {
+                return Some(Err(e));
             }
         }
-        let mut data_rows = data_builder.to_rows();
-        if !data_rows.is_empty() {
-            let f_item = data_rows.remove(0);
-            Some(f_item)
+        Some(Ok(event))
+    }
+
+    fn process_admin_message(&mut self, msg: Message) -> Option<SubscriptionMsg<R>> {
+        let m_type = msg.message_type();
+        if m_type == *SLOW_CONSUMER_WARNING {
+            self.slow_consumer = true;
+            Some(SubscriptionMsg::SlowConsumerWarning)
+        } else if m_type == *SLOW_CONSUMER_WARNING_CLEARED {
+            self.slow_consumer = false;
+            Some(SubscriptionMsg::SlowConsumerWarningCleared)
+        } else if m_type == *DATA_LOSS {
+            let dropped = msg
+                .element()
+                .element_value::<i64>("numMessagesDropped")
+                .unwrap_or_default()
+                .max(0) as u64;
+            self.data_loss += dropped;
+
+            let mut reg = self.registry.lock().unwrap();
+            let mut tickers = vec![];
+            for index in 0..msg.num_correlation_ids() {
+                let cid = match msg.correlation_id(index) {
+                    Some(cid) => cid.value,
+                    None => continue,
+                };
+                if let Some(info) = reg.get_mut(&cid) {
+                    info.data_loss += dropped;
+                    tickers.push((info.ticker.clone(), info.data_loss));
+                }
+            }
+            Some(SubscriptionMsg::DataLoss {
+                dropped,
+                total: self.data_loss,
+                tickers,
+            })
         } else {
             None
         }
     }
 
+    /// Merge the fields of the message into a pending update of the same subscription
+    fn conflate(&mut self, message: &Message, cid: u64) -> bool {
+        let merge = match self.merge {
+            Some(merge) => merge,
+            None => return false,
+        };
+        let reg = self.registry.lock().unwrap();
+        let requested_fields = match reg.get(&cid) {
+            Some(info) => &info.requested_fields,
+            None => return false,
+        };
+        let pending = self.message_buffer.iter_mut().rev().find_map(|m| match m {
+            (Some(c), SubscriptionMsg::Data { data, .. }) if *c == cid => Some(data),
+            _ => None,
+        });
+        match pending {
+            Some(data) => {
+                merge(&mut data.data, message, requested_fields);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Buffer a message of the subscription, applying the slow consumer policy
+    fn push_message(&mut self, cid: Option<u64>, msg: SubscriptionMsg<R>) {
+        // Values without merge support fall back to the last value
+        let last_value = match self.policy {
+            SlowConsumerPolicy::LastValue => true,
+            SlowConsumerPolicy::Conflate => self.merge.is_none(),
+            SlowConsumerPolicy::Deliver => false,
+        };
+        if self.slow_consumer && last_value {
+            if let (Some(cid), SubscriptionMsg::Data { .. }) = (cid, &msg) {
+                let pending = self.message_buffer.iter().position(
+                    |m| matches!(m, (Some(c), SubscriptionMsg::Data { .. }) if *c == cid),
+                );
+                if let Some(pos) = pending {
+                    self.message_buffer.remove(pos);
+                }
+            }
+        }
+        self.message_buffer.push_back((cid, msg));
+    }
+
+    fn process_event(&mut self, event: Event) {
+        let event_type = event.event_type;
+        for msg in event.messages() {
+            let cid = match event_type {
+                EventType::SessionStatus | EventType::Admin => None,
+                _ => msg.correlation_id(0).map(|cid| cid.value),
+            };
+            if let Some(cid) = cid {
+                if self.slow_consumer
+                    && self.policy == SlowConsumerPolicy::Conflate
+                    && event_type == EventType::SubscriptionData
+                    && self.conflate(&msg, cid)
+                {
+                    continue;
+                }
+            }
+            if let Some(msg) = self.process_raw_event(msg, event_type) {
+                self.push_message(cid, msg);
+            }
+        }
+    }
+
+    /// Drain all events already queued in the session
+    fn drain_pending(&mut self) {
+        // Replayed events are delivered one by one
+        if self.replay.is_some() {
+            return;
+        }
+        loop {
+            let mut event_ptr = std::ptr::null_mut();
+            let res = unsafe { blpapi_Session_tryNextEvent(self.session_ptr, &mut event_ptr) };
+            if res != 0 {
+                break;
+            }
+            let event = EventBuilder::default().ptr(event_ptr).build();
+            if let Some(recorder) = &self.recorder {
+                if let Err(e) = recorder.record(&event) {
+                    log::warn!("Recording event failed: {}", e);
+                }
+            }
+            self.process_event(event);
+        }
+    }
+
     pub fn process_raw_event(
         &mut self,
         msg: Message,
         event_type: EventType,
     ) -> Option<SubscriptionMsg<R>> {
+        // Session status messages are not bound to a subscription
+        if event_type == EventType::SessionStatus {
+            let failover = self.connection.process_message(&msg);
+            let m_type = msg.message_type();
+            return if m_type == *SESSION_TERMINATED {
+                self.exit = true;
+                Some(SubscriptionMsg::Terminated)
+            } else if m_type == *SESSION_CONNECTION_DOWN {
+                Some(SubscriptionMsg::ConnectionDown)
+            } else if m_type == *SESSION_CONNECTION_UP {
+                match failover {
+                    Some(ConnectionEvent::Failover { from, to }) => {
+                        self.push_message(None, SubscriptionMsg::ConnectionUp);
+                        Some(SubscriptionMsg::Failover { from, to })
+                    }
+                    _ => Some(SubscriptionMsg::ConnectionUp),
+                }
+            } else {
+                None
+            };
+        }
+
+        if event_type == EventType::Admin {
+            return self.process_admin_message(msg);
+        }
+
         let cid = msg.correlation_id(0)?.value;
-        let reg = self.registry.lock().unwrap();
-        let info = reg.get(&cid)?;
+        let mut reg = self.registry.lock().unwrap();
+        let info = reg.get_mut(&cid)?;
 
         match event_type {
             EventType::SubscriptionData | EventType::PartialResponse => {
-                let ticker = info.ticker.clone();
                 self.subscription_status = SubscriptionStatus::Subscribed;
-                let data = self.process_subscription_message(&msg, ticker, &info.requested_fields);
-                if let Some(data) = data {
-                    let ticker = info.ticker.clone();
-                    Some(SubscriptionMsg::Data { ticker, data })
-                } else {
-                    None
-                }
+                let data = (self.decoder)(&msg, info)?;
+                let ticker = info.ticker.clone();
+                let data = DataSeries {
+                    ticker: ticker.clone(),
+                    eids: vec![],
+                    data,
+                };
+                Some(SubscriptionMsg::Data { ticker, data })
             }
 
             EventType::SubscriptionStatus => {
                 let ticker = info.ticker.clone();
                 let m_type = msg.message_type();
-
-                if m_type == *SUBSCRIPTION_FAILURE || m_type == *SUBSCRIPTION_TERMINATED {
-                    Some(SubscriptionMsg::StatusChange {
-                        ticker,
-                        status: SubscriptionStatus::Cancelled,
-                    })
-                } else {
-                    None
-                }
-            }
-
-            EventType::SessionStatus => {
-                let m_type = msg.message_type();
-                if m_type == *SESSION_TERMINATED {
-                    self.exit = true;
-                    Some(SubscriptionMsg::Terminated)
+                let ele = msg.element();
+                let is_stream_change = m_type == *SUBSCRIPTION_STREAMS_ACTIVATED
+                    || m_type == *SUBSCRIPTION_STREAMS_DEACTIVATED;
+
+                let status = if m_type == *SUBSCRIPTION_STARTED {
+                    SubscriptionStatus::Subscribed
+                } else if m_type == *SUBSCRIPTION_FAILURE {
+                    SubscriptionStatus::Failed
+                } else if m_type == *SUBSCRIPTION_TERMINATED {
+                    SubscriptionStatus::Cancelled
+                } else if is_stream_change {
+                    let active = m_type == *SUBSCRIPTION_STREAMS_ACTIVATED;
+                    if let Some(streams) = ele.get_element("streams") {
+                        for stream in streams.values::<Element>() {
+                            let state = StreamState::from_element(&stream, active);
+                            match info.streams.iter_mut().find(|s| s.id == state.id) {
+                                Some(known) => *known = state,
+                                None => info.streams.push(state),
+                            }
+                        }
+                    }
+                    info.status
                 } else {
-                    None
+                    return None;
+                };
+
+                let reason = ele
+                    .get_element("reason")
+                    .map(|r| SubscriptionReason::from_element(&r));
+                info.status = status;
+                // Stream changes keep the reason of the last status change
+                if !is_stream_change || reason.is_some() {
+                    info.reason = reason.clone();
                 }
+                Some(SubscriptionMsg::StatusChange {
+                    ticker,
+                    status,
+                    reason,
+                    streams: info.streams.clone(),
+                })
             }
 
             _ => None,
@@ -481,14 +886,11 @@ where
     }
 }
 
-impl<R> Iterator for SubscriptionStream<R>
-where
-    R: RefData,
-{
+impl<R> Iterator for SubscriptionStream<R> {
     type Item = Result<SubscriptionMsg<R>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(msg) = self.message_buffer.pop_front() {
+        if let Some((_, msg)) = self.message_buffer.pop_front() {
             return Some(Ok(msg));
         }
 
@@ -496,22 +898,15 @@ where
             if self.exit {
                 return None;
             }
-            let mut event_ptr = std::ptr::null_mut();
-            let res = unsafe { blpapi_Session_nextEvent(self.session_ptr, &mut event_ptr, 0) };
-
-            if res != 0 {
-                return Some(Err(Error::InternalError));
-            }
-
-            let event = EventBuilder::default().ptr(event_ptr).build();
-            let event_type = event.event_type;
-
-            for msg in event.messages() {
-                if let Some(msg) = self.process_raw_event(msg, event_type) {
-                    self.message_buffer.push_back(msg);
-                }
+            let event = match self.next_event()? {
+                Ok(event) => event,
+                Err(e) => return Some(Err(e)),
+            };
+            self.process_event(event);
+            if self.slow_consumer && self.policy != SlowConsumerPolicy::Deliver {
+                self.drain_pending();
             }
-            if let Some(msg) = self.message_buffer.pop_front() {
+            if let Some((_, msg)) = self.message_buffer.pop_front() {
                 return Some(Ok(msg));
             }
         }
@@ -0,0 +1,253 @@
+use crate::{
+    constant::DataType,
+    data_series::{FieldSeries, FieldTypes},
+    json::Json,
+    session::Session,
+    Error,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Default time to live of cached fields, one week
+pub const DEFAULT_FIELD_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A cached field with the time it was fetched from //blp/apiflds
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedField {
+    pub field: FieldSeries,
+    pub fetched: SystemTime,
+}
+
+/// Local cache of //blp/apiflds field information, keyed by mnemonic and field id
+///
+/// # Note
+/// Lookups are offline, expired fields are treated as missing.
+/// `field_info` only requests missing or expired fields.
+#[derive(Debug, Clone)]
+pub struct FieldCache {
+    pub path: Option<PathBuf>,
+    pub ttl: Duration,
+    fields: HashMap<String, CachedField>,
+    ids: HashMap<String, String>,
+}
+
+impl Default for FieldCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_FIELD_TTL)
+    }
+}
+
+impl FieldCache {
+    /// In memory cache
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            path: None,
+            ttl,
+            fields: HashMap::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Cache persisted at the path, existing entries are loaded
+    pub fn open<P: AsRef<Path>>(path: P, ttl: Duration) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let mut cache = Self::new(ttl);
+        if path.exists() {
+            cache.load_json(&fs::read_to_string(&path)?)?;
+        }
+        cache.path = Some(path);
+        Ok(cache)
+    }
+
+    /// Write the cache to its path
+    pub fn save(&self) -> Result<(), Error> {
+        match &self.path {
+            Some(path) => self.save_to(path),
+            None => Err(Error::struct_error(
+                "FieldCache",
+                "save",
+                "Cache has no path",
+            )),
+        }
+    }
+
+    /// Write the cache to the path
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, self.to_json())?;
+        Ok(())
+    }
+
+    /// JSON of all cached fields, sorted by mnemonic
+    pub fn to_json(&self) -> String {
+        let mut fields: Vec<&CachedField> = self.fields.values().collect();
+        fields.sort_by(|a, b| a.field.mnemonic.cmp(&b.field.mnemonic));
+        let fields = fields
+            .into_iter()
+            .map(|cached| {
+                let fetched = cached
+                    .fetched
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                Json::object(vec![
+                    ("fetched", Json::Num(fetched as f64)),
+                    ("field", cached.field.to_json()),
+                ])
+            })
+            .collect();
+        Json::object(vec![("fields", Json::Arr(fields))]).to_pretty()
+    }
+
+    /// Add the fields of JSON written by `to_json`
+    pub fn load_json(&mut self, input: &str) -> Result<(), Error> {
+        let json = Json::parse(input)?;
+        let entries = json
+            .get("fields")
+            .and_then(|f| f.as_array())
+            .ok_or_else(|| Error::struct_error("FieldCache", "load_json", "Expected fields"))?;
+        for entry in entries {
+            let field = entry.get("field").and_then(FieldSeries::from_json);
+            let fetched = entry.get("fetched").and_then(|f| f.as_f64());
+            if let (Some(field), Some(fetched)) = (field, fetched) {
+                let fetched = UNIX_EPOCH + Duration::from_secs(fetched as u64);
+                self.insert_at(field, fetched);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a field fetched now
+    pub fn insert(&mut self, field: FieldSeries) {
+        self.insert_at(field, SystemTime::now());
+    }
+
+    /// Add a field fetched at the time
+    pub fn insert_at(&mut self, field: FieldSeries, fetched: SystemTime) {
+        if field.mnemonic.is_empty() {
+            return;
+        }
+        let mnemonic = field.mnemonic.to_uppercase();
+        if !field.id.is_empty() {
+            self.ids.insert(field.id.to_uppercase(), mnemonic.clone());
+        }
+        self.fields.insert(mnemonic, CachedField { field, fetched });
+    }
+
+    /// Number of cached fields, including expired ones
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Remove all expired fields
+    pub fn purge_expired(&mut self) {
+        let now = SystemTime::now();
+        let ttl = self.ttl;
+        self.fields
+            .retain(|_, cached| !is_expired(cached, now, ttl));
+        let fields = &self.fields;
+        self.ids.retain(|_, mnemonic| fields.contains_key(mnemonic));
+    }
+
+    fn cached(&self, key: &str) -> Option<&CachedField> {
+        let key = key.to_uppercase();
+        let mnemonic = self.ids.get(&key).unwrap_or(&key);
+        self.fields.get(mnemonic)
+    }
+
+    /// Field of the mnemonic or field id, `None` if missing or expired
+    pub fn get(&self, key: &str) -> Option<&FieldSeries> {
+        self.cached(key)
+            .filter(|cached| !is_expired(cached, SystemTime::now(), self.ttl))
+            .map(|cached| &cached.field)
+    }
+
+    /// Data type of the field, e.g. `DataType::BlpFloat64` for `Double`
+    pub fn data_type(&self, key: &str) -> Option<DataType> {
+        let field = self.get(key)?;
+        match field.field_type.as_deref() {
+            Some("BulkFormat") => Some(DataType::BlpSequence),
+            _ => field.data_type.as_deref().map(DataType::from),
+        }
+    }
+
+    /// Category of the field
+    pub fn category(&self, key: &str) -> Option<&str> {
+        self.get(key)?.field_category.as_deref()
+    }
+
+    /// Whether the field can be used as override
+    ///
+    /// # Note
+    /// Read from the `overridable` property of the field, otherwise `true` if a cached
+    /// field lists it in its overrides. `None` if the field is not cached or nothing is known.
+    pub fn is_overridable(&self, key: &str) -> Option<bool> {
+        let field = self.get(key)?;
+        let property = field
+            .field_property
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("overridable"));
+        if let Some((_, value)) = property {
+            return Some(matches!(
+                value.to_ascii_lowercase().as_str(),
+                "true" | "yes" | "1"
+            ));
+        }
+        let now = SystemTime::now();
+        let listed = self
+            .fields
+            .values()
+            .filter(|cached| !is_expired(cached, now, self.ttl))
+            .any(|cached| {
+                cached.field.overrides.iter().any(|o| {
+                    o.eq_ignore_ascii_case(&field.id) || o.eq_ignore_ascii_case(&field.mnemonic)
+                })
+            });
+        listed.then_some(true)
+    }
+
+    /// Fields of the mnemonics or ids, only missing or expired fields are requested
+    pub fn field_info(
+        &mut self,
+        session: &mut Session,
+        keys: &[&str],
+    ) -> Result<Vec<FieldSeries>, Error> {
+        let missing: Vec<&str> = keys
+            .iter()
+            .filter(|key| self.get(key).is_none())
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            for field in session.field_info_for(&missing, None, None)? {
+                self.insert(field);
+            }
+        }
+        Ok(keys
+            .iter()
+            .filter_map(|key| self.get(key).cloned())
+            .collect())
+    }
+
+    /// Request and cache all fields of the field type, returns the number of fields
+    pub fn prefetch(&mut self, session: &mut Session, field: FieldTypes) -> Result<usize, Error> {
+        let fields = session.field_list_all(field)?;
+        let len = fields.len();
+        for field in fields {
+            self.insert(field);
+        }
+        Ok(len)
+    }
+}
+
+fn is_expired(cached: &CachedField, now: SystemTime, ttl: Duration) -> bool {
+    now.duration_since(cached.fetched)
+        .map(|age| age > ttl)
+        .unwrap_or(false)
+}
@@ -0,0 +1,319 @@
+use crate::Error;
+use std::{iter::Peekable, str::Chars};
+
+/// Minimal JSON tree, object keys keep their insertion order
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    /// Integer, kept exact beyond the 53 bits of a float
+    Int(i64),
+    /// Float, NaN and infinity are written as the bare words `NaN`, `Infinity` and `-Infinity`
+    Num(f64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Create an object from static keys
+    pub(crate) fn object(fields: Vec<(&str, Json)>) -> Json {
+        Json::Obj(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Create a string or null
+    pub(crate) fn opt_str(value: &Option<String>) -> Json {
+        match value {
+            Some(value) => Json::Str(value.clone()),
+            None => Json::Null,
+        }
+    }
+
+    /// Value of the key of an object
+    pub(crate) fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Obj(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Num(n) => Some(*n),
+            Json::Int(n) => Some(*n as f64),
+            Json::Str(s) => match s.as_str() {
+                "NaN" => Some(f64::NAN),
+                "Infinity" => Some(f64::INFINITY),
+                "-Infinity" => Some(f64::NEG_INFINITY),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_i64(&self) -> Option<i64> {
+        match self {
+            Json::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Arr(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Json::Obj(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Pretty printed JSON
+    pub(crate) fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, Some(0));
+        out.push('\n');
+        out
+    }
+
+    /// JSON on a single line
+    pub(crate) fn to_compact(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, None);
+        out
+    }
+
+    /// Write the JSON, pretty printed with the indent level if given
+    fn write(&self, out: &mut String, indent: Option<usize>) {
+        let pad = |out: &mut String, n: Option<usize>| {
+            if let Some(n) = n {
+                out.push('\n');
+                out.push_str(&"  ".repeat(n));
+            }
+        };
+        let inner = indent.map(|n| n + 1);
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(&b.to_string()),
+            Json::Int(n) => out.push_str(&n.to_string()),
+            Json::Num(n) if n.is_nan() => out.push_str("NaN"),
+            Json::Num(n) if n.is_infinite() => match n.is_sign_positive() {
+                true => out.push_str("Infinity"),
+                false => out.push_str("-Infinity"),
+            },
+            Json::Num(n) => out.push_str(&n.to_string()),
+            Json::Str(s) => write_str(out, s),
+            Json::Arr(values) if values.is_empty() => out.push_str("[]"),
+            Json::Arr(values) => {
+                out.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    pad(out, inner);
+                    value.write(out, inner);
+                }
+                pad(out, indent);
+                out.push(']');
+            }
+            Json::Obj(fields) if fields.is_empty() => out.push_str("{}"),
+            Json::Obj(fields) => {
+                out.push('{');
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    pad(out, inner);
+                    write_str(out, key);
+                    out.push(':');
+                    if indent.is_some() {
+                        out.push(' ');
+                    }
+                    value.write(out, inner);
+                }
+                pad(out, indent);
+                out.push('}');
+            }
+        }
+    }
+
+    /// Parse a JSON document
+    pub(crate) fn parse(input: &str) -> Result<Json, Error> {
+        let mut chars = input.chars().peekable();
+        let value = parse_value(&mut chars)?;
+        skip_ws(&mut chars);
+        match chars.next() {
+            None => Ok(value),
+            Some(c) => Err(parse_error(&format!("Unexpected trailing '{}'", c))),
+        }
+    }
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn parse_error(msg: &str) -> Error {
+    Error::struct_error("Json", "parse", msg)
+}
+
+fn skip_ws(chars: &mut Peekable<Chars>) {
+    while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+fn expect_word(chars: &mut Peekable<Chars>, word: &str, value: Json) -> Result<Json, Error> {
+    for expected in word.chars() {
+        if chars.next() != Some(expected) {
+            return Err(parse_error(&format!("Expected '{}'", word)));
+        }
+    }
+    Ok(value)
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<Json, Error> {
+    skip_ws(chars);
+    match chars.peek() {
+        Some('n') => expect_word(chars, "null", Json::Null),
+        Some('t') => expect_word(chars, "true", Json::Bool(true)),
+        Some('f') => expect_word(chars, "false", Json::Bool(false)),
+        Some('N') => expect_word(chars, "NaN", Json::Num(f64::NAN)),
+        Some('I') => expect_word(chars, "Infinity", Json::Num(f64::INFINITY)),
+        Some('"') => parse_string(chars).map(Json::Str),
+        Some('[') => {
+            chars.next();
+            let mut values = vec![];
+            skip_ws(chars);
+            if chars.peek() == Some(&']') {
+                chars.next();
+                return Ok(Json::Arr(values));
+            }
+            loop {
+                values.push(parse_value(chars)?);
+                skip_ws(chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some(']') => return Ok(Json::Arr(values)),
+                    _ => return Err(parse_error("Expected ',' or ']'")),
+                }
+            }
+        }
+        Some('{') => {
+            chars.next();
+            let mut fields = vec![];
+            skip_ws(chars);
+            if chars.peek() == Some(&'}') {
+                chars.next();
+                return Ok(Json::Obj(fields));
+            }
+            loop {
+                skip_ws(chars);
+                let key = parse_string(chars)?;
+                skip_ws(chars);
+                if chars.next() != Some(':') {
+                    return Err(parse_error("Expected ':'"));
+                }
+                fields.push((key, parse_value(chars)?));
+                skip_ws(chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some('}') => return Ok(Json::Obj(fields)),
+                    _ => return Err(parse_error("Expected ',' or '}'")),
+                }
+            }
+        }
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let mut number = String::new();
+            while let Some(c) = chars.peek() {
+                match c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                    true => number.push(*c),
+                    false => break,
+                }
+                chars.next();
+            }
+            if number == "-" && chars.peek() == Some(&'I') {
+                return expect_word(chars, "Infinity", Json::Num(f64::NEG_INFINITY));
+            }
+            if let Ok(int) = number.parse::<i64>() {
+                return Ok(Json::Int(int));
+            }
+            number
+                .parse::<f64>()
+                .map(Json::Num)
+                .map_err(|_| parse_error(&format!("Invalid number '{}'", number)))
+        }
+        _ => Err(parse_error("Unexpected token")),
+    }
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, Error> {
+    if chars.next() != Some('"') {
+        return Err(parse_error("Expected '\"'"));
+    }
+    let mut out = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(out),
+            Some('\\') => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('b') => out.push('\u{8}'),
+                Some('f') => out.push('\u{c}'),
+                Some('u') => {
+                    let mut code = parse_hex4(chars)?;
+                    // high surrogate, combined with the low surrogate of the next escape
+                    if (0xD800..0xDC00).contains(&code) {
+                        let mut next = chars.clone();
+                        if next.next() == Some('\\') && next.next() == Some('u') {
+                            let low = parse_hex4(&mut next)?;
+                            if (0xDC00..0xE000).contains(&low) {
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                                *chars = next;
+                            }
+                        }
+                    }
+                    out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                Some(c) => out.push(c),
+                None => return Err(parse_error("Unterminated string")),
+            },
+            Some(c) => out.push(c),
+            None => return Err(parse_error("Unterminated string")),
+        }
+    }
+}
+
+fn parse_hex4(chars: &mut Peekable<Chars>) -> Result<u32, Error> {
+    let hex: String = chars.by_ref().take(4).collect();
+    u32::from_str_radix(&hex, 16).map_err(|_| parse_error("Invalid unicode escape"))
+}
@@ -1,5 +1,7 @@
 pub mod abstract_session;
 pub mod auth_options;
+pub mod codegen;
+pub mod connection;
 pub mod constant;
 pub mod core;
 pub mod correlation_id;
@@ -9,24 +11,38 @@ pub mod element;
 pub mod errors;
 pub mod event;
 pub mod event_dispatcher;
+pub mod field_cache;
 pub mod identity;
+mod json;
+pub mod logging;
 pub mod macros;
+pub mod market_bar;
+pub mod market_depth;
+pub mod market_list;
 pub mod message;
 pub mod message_iterator;
 pub mod name;
 pub mod names;
 pub mod overrides;
+pub mod recording;
 pub mod ref_data;
 pub mod request;
+pub mod request_control;
 pub mod schema;
+pub mod schema_dump;
 pub mod service;
 pub mod session;
+pub mod session_config;
 pub mod session_options;
 pub mod socks_5_config;
 pub mod subscription_list;
+pub mod supervisor;
 pub mod time_series;
+pub mod time_series_iter;
 pub mod tls_options;
+pub mod validation;
 pub mod version_info;
+pub mod vwap;
 pub mod zfp_util;
 
 #[cfg(feature = "derive")]
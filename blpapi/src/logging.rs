@@ -0,0 +1,186 @@
+use crate::Error;
+use blpapi_sys::*;
+use std::{
+    ffi::{c_char, c_int, CStr, CString},
+    path::{Path, PathBuf},
+};
+
+/// Target of the log records forwarded from the C library
+pub const LOG_TARGET: &str = "blpapi::native";
+
+/// Severity of the C library log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum LogSeverity {
+    Off,
+    Fatal,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogSeverity {
+    /// `log` level of the severity, `None` for `Off`
+    pub fn level(&self) -> Option<log::Level> {
+        match self {
+            LogSeverity::Off => None,
+            LogSeverity::Fatal | LogSeverity::Error => Some(log::Level::Error),
+            LogSeverity::Warn => Some(log::Level::Warn),
+            LogSeverity::Info => Some(log::Level::Info),
+            LogSeverity::Debug => Some(log::Level::Debug),
+            LogSeverity::Trace => Some(log::Level::Trace),
+        }
+    }
+}
+
+#[allow(non_upper_case_globals)]
+impl From<c_int> for LogSeverity {
+    fn from(arg: c_int) -> Self {
+        match arg as blpapi_Logging_Severity_t {
+            blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_OFF => LogSeverity::Off,
+            blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_FATAL => LogSeverity::Fatal,
+            blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_ERROR => LogSeverity::Error,
+            blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_WARN => LogSeverity::Warn,
+            blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_INFO => LogSeverity::Info,
+            blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_DEBUG => LogSeverity::Debug,
+            _ => LogSeverity::Trace,
+        }
+    }
+}
+
+impl From<LogSeverity> for blpapi_Logging_Severity_t {
+    fn from(arg: LogSeverity) -> Self {
+        match arg {
+            LogSeverity::Off => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_OFF,
+            LogSeverity::Fatal => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_FATAL,
+            LogSeverity::Error => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_ERROR,
+            LogSeverity::Warn => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_WARN,
+            LogSeverity::Info => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_INFO,
+            LogSeverity::Debug => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_DEBUG,
+            LogSeverity::Trace => blpapi_Logging_Severity_t_blpapi_Logging_SEVERITY_TRACE,
+        }
+    }
+}
+
+/// Most verbose severity enabled by the level filter, e.g. `LevelFilter::Info` is `Info`
+impl From<log::LevelFilter> for LogSeverity {
+    fn from(arg: log::LevelFilter) -> Self {
+        match arg {
+            log::LevelFilter::Off => LogSeverity::Off,
+            log::LevelFilter::Error => LogSeverity::Error,
+            log::LevelFilter::Warn => LogSeverity::Warn,
+            log::LevelFilter::Info => LogSeverity::Info,
+            log::LevelFilter::Debug => LogSeverity::Debug,
+            log::LevelFilter::Trace => LogSeverity::Trace,
+        }
+    }
+}
+
+/// Forward the C library log records at or above the threshold to `log`
+///
+/// # Note
+/// Records use the target `blpapi::native`, the message is prefixed with
+/// the thread id and category of the record.
+/// `tracing` subscribers receive them through `tracing-log`.
+pub fn register_logger(threshold: LogSeverity) -> Result<(), Error> {
+    let res = unsafe { blpapi_Logging_registerCallback(Some(log_callback), threshold.into()) };
+    Error::check(res)
+}
+
+/// Forward the C library log records enabled by `log::max_level`
+pub fn register_default_logger() -> Result<(), Error> {
+    register_logger(log::max_level().into())
+}
+
+/// Stop forwarding the C library log records
+pub fn unregister_logger() -> Result<(), Error> {
+    let res = unsafe { blpapi_Logging_registerCallback(None, LogSeverity::Off.into()) };
+    Error::check(res)
+}
+
+/// Log a test record through the C library, e.g. to check the registered logger
+pub fn log_test_message(severity: LogSeverity) {
+    unsafe { blpapi_Logging_logTestMessage(severity.into()) }
+}
+
+unsafe extern "C" fn log_callback(
+    thread_id: blpapi_UInt64_t,
+    severity: c_int,
+    _timestamp: blpapi_Datetime_t,
+    category: *const c_char,
+    message: *const c_char,
+) {
+    let level = match LogSeverity::from(severity).level() {
+        Some(level) => level,
+        None => return,
+    };
+    let category = match category.is_null() {
+        true => Default::default(),
+        false => CStr::from_ptr(category).to_string_lossy(),
+    };
+    let message = match message.is_null() {
+        true => Default::default(),
+        false => CStr::from_ptr(message).to_string_lossy(),
+    };
+    // Panics of the logger must not unwind into C
+    let _ = std::panic::catch_unwind(|| {
+        log::logger().log(
+            &log::Record::builder()
+                .level(level)
+                .target(LOG_TARGET)
+                .args(format_args!("[{}] {}: {}", thread_id, category, message))
+                .build(),
+        )
+    });
+}
+
+/// Native log file of the C library, see `blpapi_Logging_configureLogging`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileLogging {
+    pub path: PathBuf,
+    pub severity: LogSeverity,
+    pub rollover_file_count: i32,
+    pub rollover_file_limit: i32,
+}
+
+impl FileLogging {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            severity: LogSeverity::default(),
+            rollover_file_count: 5,
+            rollover_file_limit: 10,
+        }
+    }
+
+    pub fn severity(mut self, severity: LogSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Number of rolled over files to keep and size limit of a file
+    pub fn rollover(mut self, file_count: i32, file_limit: i32) -> Self {
+        self.rollover_file_count = file_count;
+        self.rollover_file_limit = file_limit;
+        self
+    }
+
+    /// Configure the C library to write its log into the file
+    pub fn configure(&self) -> Result<(), Error> {
+        let path = CString::new(self.path.to_string_lossy().as_bytes()).map_err(|_| {
+            Error::struct_error("FileLogging", "configure", "Path contains a nul byte")
+        })?;
+        let severity: blpapi_Logging_Severity_t = self.severity.into();
+        let res = unsafe {
+            blpapi_Logging_configureLogging(
+                severity as c_int,
+                path.as_ptr(),
+                self.rollover_file_count,
+                self.rollover_file_limit,
+            )
+        };
+        Error::check(res)
+    }
+}
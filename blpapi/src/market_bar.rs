@@ -0,0 +1,113 @@
+use crate::{
+    event::SubscriptionDecoder,
+    message::Message,
+    names::{MARKET_BAR_END, MARKET_BAR_INTERVAL_END, MARKET_BAR_START, MARKET_BAR_UPDATE},
+    overrides::SubscribeOption,
+    service::BlpServices,
+    subscription_list::{Subscription, TickerInfo},
+};
+use chrono::{NaiveDateTime, NaiveTime};
+
+const BAR_FIELD: &str = "LAST_PRICE";
+
+/// Type of a market bar event
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarEvent {
+    /// A new bar was opened
+    Start,
+    /// The current bar changed
+    Update,
+    /// The current bar is closed
+    IntervalEnd,
+    /// The bar subscription reached its end time
+    End,
+}
+
+/// An OHLCV bar of `//blp/mktbar`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketBar {
+    pub event: Option<BarEvent>,
+    pub date_time: Option<NaiveDateTime>,
+    pub open: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub close: Option<f64>,
+    pub volume: Option<i64>,
+    pub num_ticks: Option<i64>,
+}
+
+impl MarketBar {
+    /// Create a bar from a market bar message
+    pub fn from_message(message: &Message) -> Option<Self> {
+        let m_type = message.message_type();
+        let event = if m_type == *MARKET_BAR_START {
+            BarEvent::Start
+        } else if m_type == *MARKET_BAR_UPDATE {
+            BarEvent::Update
+        } else if m_type == *MARKET_BAR_INTERVAL_END {
+            BarEvent::IntervalEnd
+        } else if m_type == *MARKET_BAR_END {
+            BarEvent::End
+        } else {
+            return None;
+        };
+
+        let ele = message.element();
+        let value = |name: &str| match ele.has_element(name) {
+            true => ele.element_value::<f64>(name),
+            false => None,
+        };
+        let count = |name: &str| match ele.has_element(name) {
+            true => ele.element_value::<i64>(name),
+            false => None,
+        };
+        let date_time = match ele.has_element("DATE_TIME") {
+            true => ele.element_value::<NaiveDateTime>("DATE_TIME"),
+            false => None,
+        };
+        Some(Self {
+            event: Some(event),
+            date_time,
+            open: value("OPEN"),
+            high: value("HIGH"),
+            low: value("LOW"),
+            close: value("CLOSE"),
+            volume: count("VOLUME"),
+            num_ticks: count("NUMBER_OF_TICKS"),
+        })
+    }
+
+    /// Decoder for market bar subscriptions
+    pub fn decoder() -> SubscriptionDecoder<MarketBar> {
+        Box::new(|message: &Message, _info: &TickerInfo| MarketBar::from_message(message))
+    }
+}
+
+/// Create a bar subscription with the interval in minutes and optional GMT start and end time
+pub fn bar_subscription<'a, S: Into<String>>(
+    ticker: S,
+    interval: u32,
+    start_time: Option<NaiveTime>,
+    end_time: Option<NaiveTime>,
+) -> Subscription<'a> {
+    let mut options = vec![SubscribeOption::new("bar_size", interval.to_string())];
+    if let Some(start) = start_time {
+        options.push(SubscribeOption::new(
+            "start_time",
+            start.format("%H:%M").to_string(),
+        ));
+    }
+    if let Some(end) = end_time {
+        options.push(SubscribeOption::new(
+            "end_time",
+            end.format("%H:%M").to_string(),
+        ));
+    }
+    Subscription {
+        ticker: ticker.into(),
+        fields: vec![BAR_FIELD],
+        options: Some(options),
+        service: BlpServices::MarketBar,
+        topic_prefix: None,
+    }
+}
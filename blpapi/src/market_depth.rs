@@ -0,0 +1,318 @@
+use crate::{
+    element::{Element, GetValue},
+    event::SubscriptionDecoder,
+    message::{Message, RecapMessage},
+    overrides::SubscribeOption,
+    service::BlpServices,
+    subscription_list::{Subscription, SubscriptionKey, TickerInfo},
+};
+use std::collections::HashMap;
+
+const MKTDEPTH_EVENT_SUBTYPE: &str = "MKTDEPTH_EVENT_SUBTYPE";
+const MD_TABLE_CMD: &str = "MD_TABLE_CMD_RT";
+const MBL_WINDOW_SIZE: &str = "MBL_WINDOW_SIZE";
+const MBO_WINDOW_SIZE: &str = "MBO_WINDOW_SIZE";
+const BOOK_PREFIXES: [&str; 2] = ["MBL", "MBO"];
+
+/// Book type of a market depth subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BookType {
+    /// Market by level
+    #[default]
+    MarketByLevel,
+    /// Market by order
+    MarketByOrder,
+}
+
+impl From<BookType> for &str {
+    fn from(arg: BookType) -> Self {
+        match arg {
+            BookType::MarketByLevel => "MBL",
+            BookType::MarketByOrder => "MBO",
+        }
+    }
+}
+
+impl BookType {
+    /// Book type of the `type` option of a subscription, market by level if not set
+    fn of(info: &TickerInfo) -> Self {
+        let mbo: &str = BookType::MarketByOrder.into();
+        let mbo = SubscribeOption::new("type", mbo).value;
+        match info
+            .options
+            .iter()
+            .flatten()
+            .any(|option| option.value == mbo)
+        {
+            true => BookType::MarketByOrder,
+            false => BookType::MarketByLevel,
+        }
+    }
+}
+
+/// Side of the order book
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+impl BookSide {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BookSide::Bid => "BID",
+            BookSide::Ask => "ASK",
+        }
+    }
+}
+
+/// Table command of a market depth update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookCommand {
+    Add,
+    Del,
+    DelAll,
+    DelBetter,
+    DelSide,
+    Exec,
+    Mod,
+    Replace,
+    ReplaceByBroker,
+    ReplaceClear,
+    ClearAll,
+    Unknown,
+}
+
+impl From<&str> for BookCommand {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "ADD" => BookCommand::Add,
+            "DEL" => BookCommand::Del,
+            "DELALL" => BookCommand::DelAll,
+            "DELBETTER" => BookCommand::DelBetter,
+            "DELSIDE" => BookCommand::DelSide,
+            "EXEC" => BookCommand::Exec,
+            "MOD" => BookCommand::Mod,
+            "REPLACE" => BookCommand::Replace,
+            "REPLACE_BY_BROKER" => BookCommand::ReplaceByBroker,
+            "REPLACE_CLEAR" => BookCommand::ReplaceClear,
+            "CLEARALL" => BookCommand::ClearAll,
+            _ => BookCommand::Unknown,
+        }
+    }
+}
+
+/// A single level of the order book
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookLevel {
+    pub price: f64,
+    pub size: i64,
+    pub num_orders: Option<i64>,
+    pub broker: Option<String>,
+}
+
+/// Order book of a ticker
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderBook {
+    pub ticker: String,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+    pub window_size: Option<usize>,
+}
+
+impl OrderBook {
+    pub fn new<S: Into<String>>(ticker: S) -> Self {
+        Self {
+            ticker: ticker.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Get the levels of one side, best level first
+    pub fn side(&self, side: BookSide) -> &[BookLevel] {
+        match side {
+            BookSide::Bid => &self.bids,
+            BookSide::Ask => &self.asks,
+        }
+    }
+
+    fn side_mut(&mut self, side: BookSide) -> &mut Vec<BookLevel> {
+        match side {
+            BookSide::Bid => &mut self.bids,
+            BookSide::Ask => &mut self.asks,
+        }
+    }
+
+    /// Best bid level
+    pub fn best_bid(&self) -> Option<&BookLevel> {
+        self.bids.first()
+    }
+
+    /// Best ask level
+    pub fn best_ask(&self) -> Option<&BookLevel> {
+        self.asks.first()
+    }
+
+    /// Remove all levels of both sides
+    pub fn clear(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+    }
+
+    /// Apply a table command at the 1-based position of the side
+    pub fn apply(
+        &mut self,
+        side: BookSide,
+        command: BookCommand,
+        position: usize,
+        level: BookLevel,
+    ) {
+        let window_size = self.window_size;
+        let index = position.saturating_sub(1);
+        let levels = self.side_mut(side);
+        match command {
+            BookCommand::Add => {
+                if index > levels.len() {
+                    levels.resize(index, BookLevel::default());
+                }
+                levels.insert(index, level);
+            }
+            BookCommand::Del => {
+                if index < levels.len() {
+                    levels.remove(index);
+                }
+            }
+            BookCommand::DelBetter => {
+                let end = (index + 1).min(levels.len());
+                levels.drain(..end);
+            }
+            BookCommand::DelAll | BookCommand::DelSide => levels.clear(),
+            BookCommand::Mod
+            | BookCommand::Exec
+            | BookCommand::Replace
+            | BookCommand::ReplaceByBroker => {
+                if index >= levels.len() {
+                    levels.resize(index + 1, BookLevel::default());
+                }
+                levels[index] = level;
+            }
+            BookCommand::ReplaceClear => {
+                if index < levels.len() {
+                    levels[index] = BookLevel::default();
+                }
+            }
+            BookCommand::ClearAll => self.clear(),
+            BookCommand::Unknown => (),
+        }
+        if let Some(window) = window_size {
+            self.bids.truncate(window);
+            self.asks.truncate(window);
+        }
+    }
+
+    /// Apply a market depth message, recaps replace the whole book
+    pub fn on_message(&mut self, message: &Message) {
+        let ele = message.element();
+        let window = field_value::<i64>(&ele, &[MBL_WINDOW_SIZE, MBO_WINDOW_SIZE]);
+        if let Some(window) = window {
+            self.window_size = Some(window.max(0) as usize);
+        }
+
+        let recap = matches!(
+            message.recap_type,
+            RecapMessage::Solicited | RecapMessage::Unsolicited
+        );
+        if recap {
+            self.on_recap(&ele);
+            return;
+        }
+
+        let side = match field_value::<String>(&ele, &[MKTDEPTH_EVENT_SUBTYPE]) {
+            Some(subtype) if subtype.starts_with("BID") => BookSide::Bid,
+            Some(subtype) if subtype.starts_with("ASK") => BookSide::Ask,
+            _ => return,
+        };
+        let command = field_value::<String>(&ele, &[MD_TABLE_CMD])
+            .map(|cmd| BookCommand::from(cmd.as_str()))
+            .unwrap_or(BookCommand::Unknown);
+        let position = position(&ele, side).unwrap_or(1);
+        self.apply(side, command, position, level(&ele, side));
+    }
+
+    /// Rebuild both sides from the tables of a recap message
+    fn on_recap(&mut self, ele: &Element) {
+        self.clear();
+        for side in [BookSide::Bid, BookSide::Ask] {
+            for prefix in BOOK_PREFIXES {
+                let table = format!("{}_TABLE_{}", prefix, side.as_str());
+                if !ele.has_element(&table) {
+                    continue;
+                }
+                let table = match ele.get_element(&table) {
+                    Some(table) => table,
+                    None => continue,
+                };
+                for (index, entry) in table.values::<Element>().enumerate() {
+                    let position = position(&entry, side).unwrap_or(index + 1);
+                    self.apply(side, BookCommand::Replace, position, level(&entry, side));
+                }
+            }
+        }
+    }
+
+    /// Decoder keeping one order book per subscription and emitting a snapshot on each update
+    pub fn decoder() -> SubscriptionDecoder<OrderBook> {
+        let mut books: HashMap<(SubscriptionKey, BookType), OrderBook> = HashMap::new();
+        Box::new(move |message: &Message, info: &TickerInfo| {
+            let book = books
+                .entry((info.key(), BookType::of(info)))
+                .or_insert_with(|| OrderBook::new(info.ticker.clone()));
+            book.on_message(message);
+            Some(book.clone())
+        })
+    }
+}
+
+/// Create a market depth subscription of the given book type
+pub fn depth_subscription<'a, S: Into<String>>(ticker: S, book_type: BookType) -> Subscription<'a> {
+    let book_type: &str = book_type.into();
+    Subscription {
+        ticker: ticker.into(),
+        fields: vec![],
+        options: Some(vec![SubscribeOption::new("type", book_type)]),
+        service: BlpServices::MarketDepth,
+        topic_prefix: None,
+    }
+}
+
+/// First available value of the given field names
+fn field_value<V: GetValue>(ele: &Element, names: &[&str]) -> Option<V> {
+    names
+        .iter()
+        .filter(|name| ele.has_element(name))
+        .find_map(|name| ele.element_value(name))
+}
+
+fn side_field<V: GetValue>(ele: &Element, side: BookSide, suffix: &str) -> Option<V> {
+    let side = side.as_str();
+    let names: Vec<String> = BOOK_PREFIXES
+        .iter()
+        .map(|prefix| format!("{}_{}{}", prefix, side, suffix))
+        .chain(std::iter::once(format!("{}{}", side, suffix)))
+        .collect();
+    let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+    field_value(ele, &names)
+}
+
+fn position(ele: &Element, side: BookSide) -> Option<usize> {
+    side_field::<i64>(ele, side, "_POSITION_RT").map(|p| p.max(1) as usize)
+}
+
+fn level(ele: &Element, side: BookSide) -> BookLevel {
+    BookLevel {
+        price: side_field(ele, side, "_RT").unwrap_or_default(),
+        size: side_field(ele, side, "_SIZE_RT").unwrap_or_default(),
+        num_orders: side_field(ele, side, "_NUM_ORDERS_RT"),
+        broker: side_field(ele, side, "_BROKER_RT"),
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    element::Element,
+    event::SubscriptionDecoder,
+    message::{Message, RecapMessage},
+    service::BlpServices,
+    subscription_list::{Subscription, SubscriptionKey, TickerInfo},
+};
+use std::collections::{BTreeSet, HashMap};
+
+const ACTION: &str = "ACTION";
+const SECURITIES: &str = "SECURITIES";
+const SECURITY: &str = "SECURITY";
+
+/// Kind of a `//blp/mktlist` subscription
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MarketListKind {
+    /// Chain of a security, e.g. the option chain of an equity
+    #[default]
+    Chain,
+    /// Constituents of a list of security ids, e.g. an index
+    Secids,
+}
+
+impl MarketListKind {
+    /// Topic prefix of the list kind
+    pub fn topic_prefix(&self) -> &'static str {
+        match self {
+            MarketListKind::Chain => "/chain/",
+            MarketListKind::Secids => "/secids/",
+        }
+    }
+}
+
+/// Action of a market list update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListAction {
+    Add,
+    Remove,
+    Replace,
+    Unknown,
+}
+
+impl From<&str> for ListAction {
+    fn from(arg: &str) -> Self {
+        match arg.to_uppercase().as_str() {
+            "ADD" | "ADDED" | "INSERT" => ListAction::Add,
+            "DEL" | "DELETE" | "DELETED" | "REMOVE" | "REMOVED" => ListAction::Remove,
+            "REPLACE" | "RECAP" | "INITPAINT" => ListAction::Replace,
+            _ => ListAction::Unknown,
+        }
+    }
+}
+
+/// Member changes of a market list with the current constituent set
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketListUpdate {
+    pub list: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub members: Vec<String>,
+}
+
+/// Current constituents of a market list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketList {
+    pub list: String,
+    pub members: BTreeSet<String>,
+}
+
+impl MarketList {
+    pub fn new<S: Into<String>>(list: S) -> Self {
+        Self {
+            list: list.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Check if the security is a member of the list
+    pub fn contains(&self, member: &str) -> bool {
+        self.members.contains(member)
+    }
+
+    /// Number of members
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Check if the list has no members
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Add members, returns the update with the members which were not yet in the list
+    pub fn add<I, S>(&mut self, members: I) -> MarketListUpdate
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let added = members
+            .into_iter()
+            .map(Into::into)
+            .filter(|member: &String| self.members.insert(member.clone()))
+            .collect();
+        self.update(added, vec![])
+    }
+
+    /// Remove members, returns the update with the members which were in the list
+    pub fn remove<I, S>(&mut self, members: I) -> MarketListUpdate
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let removed = members
+            .into_iter()
+            .map(Into::into)
+            .filter(|member: &String| self.members.remove(member))
+            .collect();
+        self.update(vec![], removed)
+    }
+
+    /// Replace all members, returns the difference to the previous set
+    pub fn replace<I, S>(&mut self, members: I) -> MarketListUpdate
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let members: BTreeSet<String> = members.into_iter().map(Into::into).collect();
+        let added = members.difference(&self.members).cloned().collect();
+        let removed = self.members.difference(&members).cloned().collect();
+        self.members = members;
+        self.update(added, removed)
+    }
+
+    fn update(&self, added: Vec<String>, removed: Vec<String>) -> MarketListUpdate {
+        MarketListUpdate {
+            list: self.list.clone(),
+            added,
+            removed,
+            members: self.members.iter().cloned().collect(),
+        }
+    }
+
+    /// Apply a market list message, recaps replace the whole set
+    ///
+    /// # Note
+    /// Updates without a known action are not applied and return `None`.
+    pub fn on_message(&mut self, message: &Message) -> Option<MarketListUpdate> {
+        let ele = message.element();
+        let recap = matches!(
+            message.recap_type,
+            RecapMessage::Solicited | RecapMessage::Unsolicited
+        );
+        let action = match recap {
+            true => ListAction::Replace,
+            false => list_action(&ele),
+        };
+        let members = members(&ele);
+        match action {
+            ListAction::Add => Some(self.add(members)),
+            ListAction::Remove => Some(self.remove(members)),
+            ListAction::Replace => Some(self.replace(members)),
+            ListAction::Unknown => None,
+        }
+    }
+
+    /// Decoder keeping one constituent set per subscription and emitting the changes of each update
+    pub fn decoder() -> SubscriptionDecoder<MarketListUpdate> {
+        let mut lists: HashMap<SubscriptionKey, MarketList> = HashMap::new();
+        Box::new(move |message: &Message, info: &TickerInfo| {
+            let list = lists
+                .entry(info.key())
+                .or_insert_with(|| MarketList::new(info.ticker.clone()));
+            list.on_message(message)
+        })
+    }
+}
+
+/// Create a market list subscription, e.g. `bsym/US/IBM` for a chain
+pub fn list_subscription<'a, S: Into<String>>(list: S, kind: MarketListKind) -> Subscription<'a> {
+    Subscription {
+        ticker: list.into(),
+        fields: vec![],
+        options: None,
+        service: BlpServices::MarketList,
+        topic_prefix: Some(kind.topic_prefix().to_string()),
+    }
+}
+
+/// Action of an update, `Unknown` without an `ACTION` element
+fn list_action(ele: &Element) -> ListAction {
+    match ele.has_element(ACTION) {
+        true => ele
+            .element_value::<String>(ACTION)
+            .map(|action| ListAction::from(action.as_str()))
+            .unwrap_or(ListAction::Unknown),
+        false => ListAction::Unknown,
+    }
+}
+
+/// Member ids of the `SECURITIES` array, given as strings or as sequences with a `SECURITY`
+fn members(ele: &Element) -> Vec<String> {
+    let list = match ele.has_element(SECURITIES) {
+        true => ele.get_element(SECURITIES),
+        false => None,
+    };
+    let list = match list {
+        Some(list) => list,
+        None => return vec![],
+    };
+    (0..list.num_values())
+        .filter_map(|index| match list.is_complex_type() {
+            true => list
+                .get_at::<Element>(index)
+                .filter(|entry| entry.has_element(SECURITY))
+                .and_then(|entry| entry.element_value::<String>(SECURITY)),
+            false => list.get_at::<String>(index),
+        })
+        .collect()
+}
@@ -447,6 +447,18 @@ impl<'a> Message<'a> {
         }
     }
 
+    /// Get the name of the service of the message, `None` for session and admin messages
+    pub fn service_name(&self) -> Option<String> {
+        unsafe {
+            let service = blpapi_Message_service(self.ptr);
+            if service.is_null() {
+                return None;
+            }
+            let name = CStr::from_ptr(blpapi_Service_name(service));
+            Some(name.to_string_lossy().into_owned())
+        }
+    }
+
     /// Get number of correlation ids
     pub fn num_correlation_ids(&self) -> usize {
         unsafe { blpapi_Message_numCorrelationIds(self.ptr) as usize }
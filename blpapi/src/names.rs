@@ -16,6 +16,8 @@ pub static REQUEST_TEMPLATE_PENDING: Lazy<Name> = Lazy::new(|| Name::new("reques
 pub static REQUEST_TEMPLATE_TERMINATED: Lazy<Name> =
     Lazy::new(|| Name::new("requestTemplateTerminated"));
 pub static REQUEST_FAILURE: Lazy<Name> = Lazy::new(|| Name::new("requestFailure"));
+pub static RESPONSE_ERROR: Lazy<Name> = Lazy::new(|| Name::new("responseError"));
+pub static REASON: Lazy<Name> = Lazy::new(|| Name::new("reason"));
 
 /// Subscription Names
 pub static SUBSCRIPTION_MARKET_DATA: Lazy<Name> = Lazy::new(|| Name::new("MarketDataEvents"));
@@ -27,6 +29,12 @@ pub static SUBSCRIPTION_STREAMS_ACTIVATED: Lazy<Name> =
 pub static SUBSCRIPTION_STREAMS_DEACTIVATED: Lazy<Name> =
     Lazy::new(|| Name::new("subscriptionStreamsDeactivated"));
 
+/// Market Bar Names
+pub static MARKET_BAR_START: Lazy<Name> = Lazy::new(|| Name::new("MarketBarStart"));
+pub static MARKET_BAR_UPDATE: Lazy<Name> = Lazy::new(|| Name::new("MarketBarUpdate"));
+pub static MARKET_BAR_INTERVAL_END: Lazy<Name> = Lazy::new(|| Name::new("MarketBarIntervalEnd"));
+pub static MARKET_BAR_END: Lazy<Name> = Lazy::new(|| Name::new("MarketBarEnd"));
+
 /// Token Names
 pub static TOKEN_GENERATION_SUCCESS: Lazy<Name> = Lazy::new(|| Name::new("tokenGenerationSuccess"));
 pub static TOKEN_GENERATION_FAILURE: Lazy<Name> = Lazy::new(|| Name::new("tokenGenerationFailure"));
@@ -0,0 +1,513 @@
+use crate::{
+    constant::DataType,
+    correlation_id::CorrelationId,
+    datetime::HighPrecisionDateTime,
+    element::Element,
+    event::{Event, EventBuilder, EventType},
+    json::Json,
+    message::Message,
+    name::Name,
+    service::Service,
+    Error,
+};
+use blpapi_sys::*;
+use std::{
+    collections::{HashMap, VecDeque},
+    ffi::{c_int, CString},
+    fmt,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    ptr,
+    sync::{Arc, Mutex},
+};
+
+/// A message of a recorded event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordedMessage {
+    pub message_type: String,
+    pub service: Option<String>,
+    pub correlation_ids: Vec<u64>,
+    pub request_id: Option<String>,
+    pub recap_type: i32,
+    pub fragment_type: i32,
+    /// Nanoseconds since epoch
+    pub time_received: Option<i64>,
+    /// Element tree of the message as JSON
+    pub elements: String,
+}
+
+impl RecordedMessage {
+    /// Capture the properties and the full element tree of the message
+    pub fn from_message(message: &Message) -> Self {
+        let mut correlation_ids: Vec<(usize, u64)> = message
+            .correlation_ids
+            .iter()
+            .map(|(index, id)| (*index, id.value))
+            .collect();
+        correlation_ids.sort();
+
+        let mut time_point = blpapi_TimePoint_t { d_value: 0 };
+        let time_received =
+            match unsafe { blpapi_Message_timeReceived(message.ptr, &mut time_point) } {
+                0 => Some(time_point.d_value),
+                _ => None,
+            };
+        let (recap_type, fragment_type) = unsafe {
+            (
+                blpapi_Message_recapType(message.ptr),
+                blpapi_Message_fragmentType(message.ptr),
+            )
+        };
+
+        Self {
+            message_type: message.message_type().to_string(),
+            service: message.service_name(),
+            correlation_ids: correlation_ids.into_iter().map(|(_, id)| id).collect(),
+            request_id: Some(message.request_id.clone()).filter(|id| !id.is_empty()),
+            recap_type,
+            fragment_type,
+            time_received,
+            elements: element_to_json(&message.element()).to_compact(),
+        }
+    }
+
+    fn to_json(&self) -> Result<Json, Error> {
+        let service = match &self.service {
+            Some(service) => Json::Str(service.clone()),
+            None => Json::Null,
+        };
+        let time_received = match self.time_received {
+            Some(time) => Json::Str(time.to_string()),
+            None => Json::Null,
+        };
+        Ok(Json::object(vec![
+            ("type", Json::Str(self.message_type.clone())),
+            ("service", service),
+            (
+                "correlationIds",
+                Json::Arr(
+                    self.correlation_ids
+                        .iter()
+                        .map(|id| Json::Str(id.to_string()))
+                        .collect(),
+                ),
+            ),
+            ("requestId", Json::opt_str(&self.request_id)),
+            ("recapType", Json::Int(self.recap_type as i64)),
+            ("fragmentType", Json::Int(self.fragment_type as i64)),
+            ("timeReceived", time_received),
+            ("elements", Json::parse(&self.elements)?),
+        ]))
+    }
+
+    fn from_json(json: &Json) -> Option<Self> {
+        let string = |key: &str| json.get(key).and_then(|v| v.as_str()).map(String::from);
+        let number = |key: &str| json.get(key).and_then(|v| v.as_i64()).unwrap_or_default();
+        let correlation_ids = json
+            .get("correlationIds")?
+            .as_array()?
+            .iter()
+            .filter_map(|id| id.as_str()?.parse().ok())
+            .collect();
+        Some(Self {
+            message_type: string("type")?,
+            service: string("service"),
+            correlation_ids,
+            request_id: string("requestId"),
+            recap_type: number("recapType") as i32,
+            fragment_type: number("fragmentType") as i32,
+            time_received: string("timeReceived").and_then(|t| t.parse().ok()),
+            elements: json.get("elements")?.to_compact(),
+        })
+    }
+}
+
+/// An event captured by an `EventRecorder`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    pub event_type: EventType,
+    pub messages: Vec<RecordedMessage>,
+}
+
+impl RecordedEvent {
+    pub fn from_event(event: &Event) -> Self {
+        Self {
+            event_type: event.event_type,
+            messages: event
+                .messages()
+                .map(|message| RecordedMessage::from_message(&message))
+                .collect(),
+        }
+    }
+
+    /// Single line of the recording
+    pub fn to_line(&self) -> Result<String, Error> {
+        let messages = self
+            .messages
+            .iter()
+            .map(|message| message.to_json())
+            .collect::<Result<Vec<_>, Error>>()?;
+        let event_type: c_int = self.event_type.into();
+        let json = Json::object(vec![
+            ("eventType", Json::Int(event_type as i64)),
+            ("messages", Json::Arr(messages)),
+        ]);
+        Ok(json.to_compact())
+    }
+}
+
+/// A line of a recording
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordLine {
+    /// XML schema of a service opened while recording
+    Service {
+        name: String,
+        schema: String,
+    },
+    Event(RecordedEvent),
+}
+
+impl RecordLine {
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        let invalid = || Error::struct_error("RecordLine", "parse", "Invalid recording line");
+        let json = Json::parse(line)?;
+        if let Some(schema) = json.get("schema").and_then(|s| s.as_str()) {
+            let name = json
+                .get("service")
+                .and_then(|s| s.as_str())
+                .ok_or_else(invalid)?;
+            return Ok(RecordLine::Service {
+                name: name.to_string(),
+                schema: schema.to_string(),
+            });
+        }
+        let event_type = json
+            .get("eventType")
+            .and_then(|t| t.as_i64())
+            .ok_or_else(invalid)?;
+        let messages = json
+            .get("messages")
+            .and_then(|m| m.as_array())
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|message| RecordedMessage::from_json(message).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(RecordLine::Event(RecordedEvent {
+            event_type: EventType::from(event_type as c_int),
+            messages,
+        }))
+    }
+}
+
+/// Records every event a `Session` receives as one JSON line per event
+///
+/// # Note
+/// The schemas of the services opened while recording are written as well,
+/// they are needed to rebuild the messages on replay.
+#[derive(Clone)]
+pub struct EventRecorder {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl fmt::Debug for EventRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventRecorder").finish()
+    }
+}
+
+impl EventRecorder {
+    pub fn new<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(Box::new(writer))),
+        }
+    }
+
+    /// Record into a new file
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+
+    fn write_line(&self, line: &str) -> Result<(), Error> {
+        let mut writer = self.writer.lock().unwrap();
+        writer.write_all(line.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Record the schema of the service
+    pub fn record_service(&self, service: &Service) -> Result<(), Error> {
+        let mut schema: Vec<u8> = vec![];
+        service.serialize(&mut schema)?;
+        let json = Json::object(vec![
+            ("service", Json::Str(service.name())),
+            (
+                "schema",
+                Json::Str(String::from_utf8_lossy(&schema).into_owned()),
+            ),
+        ]);
+        self.write_line(&json.to_compact())
+    }
+
+    /// Record the event and all its messages
+    pub fn record(&self, event: &Event) -> Result<(), Error> {
+        self.write_line(&RecordedEvent::from_event(event).to_line()?)
+    }
+}
+
+/// Replays recorded events as blpapi events, in the order they were received
+#[derive(Debug, Default)]
+pub struct EventReplay {
+    services: HashMap<String, Service>,
+    events: VecDeque<RecordedEvent>,
+}
+
+// Services are only used to look up schema definitions
+unsafe impl Send for EventReplay {}
+
+impl EventReplay {
+    /// Load the recording of the file
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Load the recording of the reader
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, Error> {
+        let mut replay = Self::default();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match RecordLine::parse(&line)? {
+                RecordLine::Service { name, schema } => {
+                    replay.services.insert(name, Service::deserialize(&schema)?);
+                }
+                RecordLine::Event(event) => replay.events.push_back(event),
+            }
+        }
+        Ok(replay)
+    }
+
+    /// Number of events left
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Service of the recording
+    pub fn service(&self, name: &str) -> Option<&Service> {
+        self.services.get(name)
+    }
+
+    /// Build the next recorded event, `None` once all events are replayed
+    pub fn next_event(&mut self) -> Option<Result<Event, Error>> {
+        let recorded = self.events.pop_front()?;
+        Some(self.build_event(&recorded))
+    }
+
+    fn build_event(&self, recorded: &RecordedEvent) -> Result<Event, Error> {
+        let mut event_ptr: *mut blpapi_Event_t = ptr::null_mut();
+        let res =
+            unsafe { blpapi_TestUtil_createEvent(&mut event_ptr, recorded.event_type.into()) };
+        Error::check(res)?;
+        let event = EventBuilder::default().ptr(event_ptr).build();
+        for message in &recorded.messages {
+            self.append_message(event_ptr, message)?;
+        }
+        Ok(event)
+    }
+
+    fn append_message(
+        &self,
+        event: *mut blpapi_Event_t,
+        message: &RecordedMessage,
+    ) -> Result<(), Error> {
+        let service = message.service.as_ref().and_then(|s| self.services.get(s));
+        let definition = definition(service, &message.message_type)?;
+        let correlation_ids: Vec<blpapi_CorrelationId_t> = message
+            .correlation_ids
+            .iter()
+            .map(|id| CorrelationId::new_u64(*id).id)
+            .collect();
+        let request_id = message
+            .request_id
+            .as_deref()
+            .map(|id| CString::new(id).unwrap_or_default());
+        let elements = CString::new(message.elements.as_str()).unwrap_or_default();
+
+        unsafe {
+            let mut properties: *mut blpapi_MessageProperties_t = ptr::null_mut();
+            Error::check(blpapi_MessageProperties_create(&mut properties))?;
+            let mut res = blpapi_MessageProperties_setCorrelationIds(
+                properties,
+                correlation_ids.as_ptr(),
+                correlation_ids.len(),
+            );
+            if res == 0 {
+                res = blpapi_MessageProperties_setRecapType(
+                    properties,
+                    message.recap_type,
+                    message.fragment_type,
+                );
+            }
+            if let (0, Some(service)) = (res, service) {
+                res = blpapi_MessageProperties_setService(properties, service.ptr);
+            }
+            if let (0, Some(request_id)) = (res, &request_id) {
+                res = blpapi_MessageProperties_setRequestId(properties, request_id.as_ptr());
+            }
+            if let (0, Some(time_received)) = (res, message.time_received) {
+                let time_point = blpapi_TimePoint_t {
+                    d_value: time_received,
+                };
+                let mut datetime: blpapi_HighPrecisionDatetime_t = std::mem::zeroed();
+                res = blpapi_HighPrecisionDatetime_fromTimePoint(&mut datetime, &time_point, 0);
+                if res == 0 {
+                    res = blpapi_MessageProperties_setTimeReceived(properties, &datetime);
+                }
+            }
+
+            let mut formatter: *mut blpapi_MessageFormatter_t = ptr::null_mut();
+            if res == 0 {
+                res = blpapi_TestUtil_appendMessage(&mut formatter, event, definition, properties);
+            }
+            blpapi_MessageProperties_destroy(properties);
+            if res == 0 {
+                res = blpapi_MessageFormatter_FormatMessageJson(formatter, elements.as_ptr());
+            }
+            if !formatter.is_null() {
+                blpapi_MessageFormatter_destroy(formatter);
+            }
+            Error::check(res)
+        }
+    }
+}
+
+/// Schema definition of a message type, searched in the events and responses of the service
+/// or in the admin messages if the message has no service
+fn definition(
+    service: Option<&Service>,
+    message_type: &str,
+) -> Result<*mut blpapi_SchemaElementDefinition_t, Error> {
+    let service = match service {
+        Some(service) => service,
+        None => {
+            let name = Name::new(message_type);
+            let mut definition: *mut blpapi_SchemaElementDefinition_t = ptr::null_mut();
+            let res =
+                unsafe { blpapi_TestUtil_getAdminMessageDefinition(&mut definition, name.ptr) };
+            Error::check(res)?;
+            return Ok(definition);
+        }
+    };
+    if let Ok(definition) = service.event_definition(message_type) {
+        return Ok(definition.ptr);
+    }
+    service
+        .operations()
+        .flat_map(|operation| operation.response_definitions().unwrap_or_default())
+        .find(|definition| definition.name == message_type)
+        .map(|definition| definition.ptr)
+        .ok_or_else(|| {
+            Error::struct_error(
+                "EventReplay",
+                "definition",
+                &format!("No schema definition for message type '{}'", message_type),
+            )
+        })
+}
+
+/// Element tree as JSON in the format of `blpapi_MessageFormatter_FormatMessageJson`
+fn element_to_json(element: &Element) -> Json {
+    match element.is_array() {
+        true => Json::Arr(
+            (0..element.num_values())
+                .map(|index| value_to_json(element, index))
+                .collect(),
+        ),
+        false => value_to_json(element, 0),
+    }
+}
+
+fn value_to_json(element: &Element, index: usize) -> Json {
+    if element.is_complex_type() {
+        let sequence = match element.is_array() {
+            true => match element.get_at::<Element>(index) {
+                Some(sequence) => sequence,
+                None => return Json::Null,
+            },
+            false => element.clone(),
+        };
+        let fields = sequence
+            .elements()
+            .filter(|child| !child.is_null())
+            .map(|child| (child.string_name(), element_to_json(&child)))
+            .collect();
+        return Json::Obj(fields);
+    }
+    if element.is_null_value(index) {
+        return Json::Null;
+    }
+    let value = match element.data_type() {
+        DataType::BlpBool => element.get_at::<bool>(index).map(Json::Bool),
+        DataType::BlpInt32 | DataType::BlpInt64 | DataType::BlpByte => {
+            element.get_at::<i64>(index).map(Json::Int)
+        }
+        DataType::BlpFloat32 | DataType::BlpFloat64 | DataType::BlpDecimal => {
+            element.get_at::<f64>(index).map(float_to_json)
+        }
+        DataType::BlpDate | DataType::BlpDatetime | DataType::BlpTime => element
+            .get_at::<HighPrecisionDateTime>(index)
+            .map(|d| Json::Str(datetime_to_json(&d.ptr))),
+        _ => element.get_at::<String>(index).map(Json::Str),
+    };
+    value.unwrap_or(Json::Null)
+}
+
+/// Float, NaN and infinity as the strings of the message formatter
+fn float_to_json(value: f64) -> Json {
+    match value {
+        v if v.is_nan() => Json::Str(String::from("NaN")),
+        v if v == f64::INFINITY => Json::Str(String::from("Infinity")),
+        v if v == f64::NEG_INFINITY => Json::Str(String::from("-Infinity")),
+        v => Json::Num(v),
+    }
+}
+
+/// ISO 8601 string of the parts set in the datetime, with picoseconds and offset
+fn datetime_to_json(datetime: &blpapi_HighPrecisionDatetime_t) -> String {
+    let dt = &datetime.datetime;
+    let parts = dt.parts as u32;
+    let mut out = String::new();
+    if parts & BLPAPI_DATETIME_DATE_PART == BLPAPI_DATETIME_DATE_PART {
+        out.push_str(&format!("{:04}-{:02}-{:02}", dt.year, dt.month, dt.day));
+    }
+    if parts & BLPAPI_DATETIME_TIME_PART == BLPAPI_DATETIME_TIME_PART {
+        if !out.is_empty() {
+            out.push('T');
+        }
+        out.push_str(&format!(
+            "{:02}:{:02}:{:02}",
+            dt.hours, dt.minutes, dt.seconds
+        ));
+        if parts & BLPAPI_DATETIME_FRACSECONDS_PART != 0 {
+            let picoseconds = dt.milliSeconds as u64 * 1_000_000_000 + datetime.picoseconds as u64;
+            // At least milliseconds, up to picoseconds
+            let fraction = format!("{:012}", picoseconds);
+            let digits = fraction.trim_end_matches('0').len().max(3);
+            out.push('.');
+            out.push_str(&fraction[..digits]);
+        }
+    }
+    if parts & BLPAPI_DATETIME_OFFSET_PART != 0 {
+        let sign = if dt.offset < 0 { '-' } else { '+' };
+        let offset = dt.offset.unsigned_abs();
+        out.push_str(&format!("{}{:02}:{:02}", sign, offset / 60, offset % 60));
+    }
+    out
+}
@@ -11,12 +11,13 @@ use crate::{
     element::{Element, SetValue},
     name::Name,
     service::Service,
+    validation::SchemaValidator,
     Error,
 };
 use blpapi_sys::*;
 use std::ffi::{c_char, CStr, CString};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum RequestTypes {
     ReferenceData,
     FieldList,
@@ -31,6 +32,15 @@ pub enum RequestTypes {
     IntradayBar,
     IntradayTick,
     Beqs,
+    /// Operation of a custom service, e.g. `TestRequest`
+    Custom(&'static str),
+}
+
+impl RequestTypes {
+    /// Operation name of the request type
+    pub fn as_str(&self) -> &'static str {
+        (*self).into()
+    }
 }
 
 impl From<RequestTypes> for &str {
@@ -51,6 +61,7 @@ impl From<RequestTypes> for &str {
             RequestTypes::IntradayBar => BLPAPI_DEFAULT_INTRADAY_BAR_DATA_REQUEST,
             RequestTypes::IntradayTick => BLPAPI_DEFAULT_INTRADAY_TICK_DATA_REQUEST,
             RequestTypes::Beqs => BLPAPI_DEFAULT_BEQS_DATA_REQUEST,
+            RequestTypes::Custom(operation) => operation,
         }
     }
 }
@@ -165,6 +176,16 @@ impl Request {
         Ok(())
     }
 
+    /// Validator for the values of the request based on its schema
+    pub fn validator(&self) -> SchemaValidator {
+        SchemaValidator::from_request(self)
+    }
+
+    /// Validate the values set on the request against its schema
+    pub fn validate(&self) -> Result<(), Error> {
+        self.validator().validate_request(self)
+    }
+
     pub fn append_complex(&mut self, name: &Name) -> Result<Element, Error> {
         let mut container = self
             .element()
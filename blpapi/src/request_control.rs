@@ -0,0 +1,118 @@
+use crate::Error;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// Interval the cancellation token is checked while waiting for events
+const POLL_INTERVAL_MS: u32 = 100;
+
+/// Token to cancel blocking requests from another thread
+///
+/// # Note
+/// Clones share the state, cancelling one cancels all requests using it.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Deadline and cancellation token of a blocking request
+#[derive(Debug, Clone, Default)]
+pub struct RequestControl {
+    pub deadline: Option<Instant>,
+    pub token: Option<CancellationToken>,
+}
+
+impl RequestControl {
+    /// Deadline relative to now
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn token(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Whether neither a deadline nor a token is set
+    pub fn is_unbounded(&self) -> bool {
+        self.deadline.is_none() && self.token.is_none()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .map(|deadline| Instant::now() >= deadline)
+            .unwrap_or(false)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token
+            .as_ref()
+            .map(|token| token.is_cancelled())
+            .unwrap_or(false)
+    }
+
+    /// Whether the request has to be stopped
+    pub fn is_stopped(&self) -> bool {
+        self.is_expired() || self.is_cancelled()
+    }
+
+    /// Timeout in ms of the next wait for an event, 0 waits forever
+    pub(crate) fn wait_ms(&self, time_out: u32) -> u32 {
+        let mut wait = time_out;
+        if let Some(deadline) = self.deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let remaining = remaining.as_millis().clamp(1, u32::MAX as u128) as u32;
+            wait = match wait {
+                0 => remaining,
+                _ => wait.min(remaining),
+            };
+        }
+        if self.token.is_some() {
+            wait = match wait {
+                0 => POLL_INTERVAL_MS,
+                _ => wait.min(POLL_INTERVAL_MS),
+            };
+        }
+        wait
+    }
+}
+
+/// Data received before a request failed, timed out or was cancelled
+#[derive(Debug)]
+pub struct Partial<T> {
+    pub data: T,
+    pub error: Error,
+}
+
+impl<T> From<Box<Partial<T>>> for Error {
+    fn from(partial: Box<Partial<T>>) -> Self {
+        partial.error
+    }
+}
+
+/// Result keeping the data received before an error
+pub type PartialResult<T> = Result<T, Box<Partial<T>>>;
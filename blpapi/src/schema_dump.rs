@@ -0,0 +1,225 @@
+use crate::{
+    constant::DataType,
+    json::Json,
+    schema::SchemaElements,
+    service::{BlpServices, Operation, Service},
+    session::Session,
+    Error,
+};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Max values of an unbounded array element
+pub const UNBOUNDED: usize = usize::MAX;
+
+/// Summary of a schema element definition
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementSummary {
+    pub name: String,
+    pub type_name: String,
+    pub data_type: DataType,
+    pub min_values: usize,
+    pub max_values: usize,
+    pub enumeration: Vec<String>,
+    pub elements: Vec<ElementSummary>,
+}
+
+impl ElementSummary {
+    /// Create the summary of a definition and all its sub elements
+    pub fn from_schema(definition: &SchemaElements) -> Self {
+        Self::from_schema_path(definition, &mut vec![])
+    }
+
+    /// Recursive types are only expanded once per path
+    fn from_schema_path(definition: &SchemaElements, path: &mut Vec<String>) -> Self {
+        let schema_type = &definition.schema_type;
+        let type_name = schema_type.name.to_string();
+
+        let mut constants: Vec<_> = schema_type.constant_list.constants.iter().collect();
+        constants.sort_by_key(|(index, _)| **index);
+        let enumeration = constants
+            .into_iter()
+            .map(|(_, constant)| constant.name().to_string())
+            .collect();
+
+        let mut elements = vec![];
+        if schema_type.is_complex_type && !path.contains(&type_name) {
+            path.push(type_name.clone());
+            for index in 0..schema_type.num_eleent_definitions() {
+                if let Ok(child) = schema_type.element_def_at(index) {
+                    elements.push(Self::from_schema_path(&child, path));
+                }
+            }
+            path.pop();
+        }
+
+        Self {
+            name: definition.name.to_string(),
+            type_name,
+            data_type: schema_type.data_type.clone(),
+            min_values: definition.min().unwrap_or_default(),
+            max_values: definition.max().unwrap_or_default(),
+            enumeration,
+            elements,
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let max_values = match self.max_values {
+            UNBOUNDED => Json::Null,
+            max => Json::Num(max as f64),
+        };
+        Json::object(vec![
+            ("name", Json::Str(self.name.clone())),
+            ("type", Json::Str(self.type_name.clone())),
+            ("dataType", Json::Str(format!("{:?}", self.data_type))),
+            ("minValues", Json::Num(self.min_values as f64)),
+            ("maxValues", max_values),
+            (
+                "enumeration",
+                Json::Arr(self.enumeration.iter().cloned().map(Json::Str).collect()),
+            ),
+            (
+                "elements",
+                Json::Arr(self.elements.iter().map(|e| e.to_json()).collect()),
+            ),
+        ])
+    }
+}
+
+/// Summary of a service operation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationSummary {
+    pub name: String,
+    pub description: String,
+    pub request: Option<ElementSummary>,
+    pub responses: Vec<ElementSummary>,
+}
+
+impl OperationSummary {
+    pub fn from_operation(operation: &Operation) -> Self {
+        let request = operation
+            .request_definition()
+            .ok()
+            .map(|definition| ElementSummary::from_schema(&definition));
+        let responses = operation
+            .response_definitions()
+            .unwrap_or_default()
+            .iter()
+            .map(ElementSummary::from_schema)
+            .collect();
+        Self {
+            name: operation.name(),
+            description: operation.description(),
+            request,
+            responses,
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        let request = match &self.request {
+            Some(request) => request.to_json(),
+            None => Json::Null,
+        };
+        Json::object(vec![
+            ("name", Json::Str(self.name.clone())),
+            ("description", Json::Str(self.description.clone())),
+            ("request", request),
+            (
+                "responses",
+                Json::Arr(self.responses.iter().map(|r| r.to_json()).collect()),
+            ),
+        ])
+    }
+}
+
+/// Summary of a service schema with all operations and event definitions
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServiceSummary {
+    pub name: String,
+    pub description: String,
+    pub operations: Vec<OperationSummary>,
+    pub events: Vec<ElementSummary>,
+}
+
+impl ServiceSummary {
+    pub fn from_service(service: &Service) -> Self {
+        Self {
+            name: service.name(),
+            description: service.description(),
+            operations: service
+                .operations()
+                .map(|operation| OperationSummary::from_operation(&operation))
+                .collect(),
+            events: service
+                .event_definitions()
+                .map(|definition| ElementSummary::from_schema(&definition))
+                .collect(),
+        }
+    }
+
+    /// Pretty printed JSON of the summary
+    pub fn to_json(&self) -> String {
+        let json = Json::object(vec![
+            ("name", Json::Str(self.name.clone())),
+            ("description", Json::Str(self.description.clone())),
+            (
+                "operations",
+                Json::Arr(self.operations.iter().map(|o| o.to_json()).collect()),
+            ),
+            (
+                "events",
+                Json::Arr(self.events.iter().map(|e| e.to_json()).collect()),
+            ),
+        ]);
+        json.to_pretty()
+    }
+}
+
+/// Files written by `dump_service`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDump {
+    pub xml: PathBuf,
+    pub json: PathBuf,
+}
+
+/// Open the service and write its XML schema and JSON summary into the directory
+/// The files are named after the service, e.g. `blp_refdata.xml` and `blp_refdata.json`
+pub fn dump_service<P: AsRef<Path>>(
+    session: &mut Session,
+    service: &BlpServices,
+    dir: P,
+) -> Result<SchemaDump, Error> {
+    session.open_service(service)?;
+    let blp_service = session.get_service(service)?;
+
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let stem = file_stem(service);
+    let xml = dir.join(format!("{}.xml", stem));
+    let json = dir.join(format!("{}.json", stem));
+
+    let mut writer = BufWriter::new(File::create(&xml)?);
+    blp_service.serialize(&mut writer)?;
+    writer.flush()?;
+
+    let summary = ServiceSummary::from_service(&blp_service);
+    fs::write(&json, summary.to_json())?;
+    Ok(SchemaDump { xml, json })
+}
+
+/// File name of a service identifier, e.g. `//blp/refdata` becomes `blp_refdata`
+pub fn file_stem(service: &BlpServices) -> String {
+    let service: &str = service.into();
+    service
+        .trim_start_matches('/')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '-' {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
@@ -1,22 +1,28 @@
 use crate::{
     core::{
-        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_API_FIELDS,
+        write_to_stream_cb, StreamWriterContext, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_API_FIELDS,
+        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CORPORATE_EARNINGS,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CURVES_TOOLKIT,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_INSTRUMENTS,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_BAR,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_DEPTH,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_LIST, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MKTDATA,
-        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_REFDATA, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_SOURCE_REF,
-        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_STATIC_MKT,
+        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_PAGE_DATA, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_REFDATA,
+        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_SOURCE_REF, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_STATIC_MKT,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_TECHNICAL_ANALYSIS,
         BLPAPI_DEFAULT_SERVICE_IDENTIFIER_VWAP,
     },
     name::Name,
     request::{Request, RequestBuilder, RequestTypes},
+    schema::SchemaElements,
     Error,
 };
 use blpapi_sys::*;
-use std::{ffi::CStr, ptr};
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    io::Write,
+    ptr,
+};
 
 pub struct Operation {
     pub(crate) ptr: *mut blpapi_Operation_t,
@@ -36,30 +42,29 @@ impl Operation {
     }
 
     /// Get the request definition
-    pub fn request_definition(&self) -> Result<(), Error> {
+    pub fn request_definition(&self) -> Result<SchemaElements, Error> {
         let mut schema_ele: *mut blpapi_SchemaElementDefinition_t = ptr::null_mut();
         let rc = unsafe { blpapi_Operation_requestDefinition(self.ptr, &mut schema_ele) };
-        println!("Req. def:{rc}");
-        Ok(())
+        Error::check(rc)?;
+        Ok(SchemaElements::default().from_ptr(schema_ele))
     }
 
     /// Number of Resonse Definition
-    pub fn num_response_definition(&self) -> Result<(), Error> {
+    pub fn num_response_definition(&self) -> usize {
         let rc = unsafe { blpapi_Operation_numResponseDefinitions(self.ptr) };
-        println!("No Response: {rc}");
-        Ok(())
+        rc.max(0) as usize
     }
 
     /// Response Definition on index
-    pub fn response_definition(&self, index: usize) -> Result<(), Error> {
+    pub fn response_definition(&self, index: usize) -> Result<SchemaElements, Error> {
         let mut schema_ele: *mut blpapi_SchemaElementDefinition_t = ptr::null_mut();
         let rc = unsafe { blpapi_Operation_responseDefinition(self.ptr, &mut schema_ele, index) };
-        println!("Resp. def:{rc}");
-        Ok(())
+        Error::check(rc)?;
+        Ok(SchemaElements::default().from_ptr(schema_ele))
     }
 
     /// Response Definition on name
-    pub fn response_definition_from_name(&self, name: Name) -> Result<(), Error> {
+    pub fn response_definition_from_name(&self, name: Name) -> Result<SchemaElements, Error> {
         let mut schema_ele: *mut blpapi_SchemaElementDefinition_t = ptr::null_mut();
         let rc = unsafe {
             blpapi_Operation_responseDefinitionFromName(
@@ -68,8 +73,15 @@ impl Operation {
                 name.ptr as *const _,
             )
         };
-        println!("Resp. def:{rc}");
-        Ok(())
+        Error::check(rc)?;
+        Ok(SchemaElements::default().from_ptr(schema_ele))
+    }
+
+    /// All response definitions of the operation
+    pub fn response_definitions(&self) -> Result<Vec<SchemaElements>, Error> {
+        (0..self.num_response_definition())
+            .map(|index| self.response_definition(index))
+            .collect()
     }
 }
 
@@ -82,7 +94,7 @@ pub enum BlpServiceStatus {
 }
 
 /// ServiceTypes
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
 pub enum BlpServices {
     MarketData,
     ReferenceData,
@@ -99,12 +111,14 @@ pub enum BlpServices {
     TechnicalAnalysis,
     CurvesToolkit,
     CorporateEarnings,
+    /// Any other service identifier, e.g. `//blp/emapisvc` or a published service
+    Custom(String),
     #[default]
     NoService,
 }
 
-impl From<&BlpServices> for &str {
-    fn from(arg: &BlpServices) -> Self {
+impl<'a> From<&'a BlpServices> for &'a str {
+    fn from(arg: &'a BlpServices) -> Self {
         match arg {
             BlpServices::MarketData => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MKTDATA,
             BlpServices::ReferenceData => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_REFDATA,
@@ -116,15 +130,39 @@ impl From<&BlpServices> for &str {
             BlpServices::MarketList => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_LIST,
             BlpServices::ApiFields => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_API_FIELDS,
             BlpServices::Instruments => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_INSTRUMENTS,
-            BlpServices::PageData => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_TECHNICAL_ANALYSIS,
+            BlpServices::PageData => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_PAGE_DATA,
             BlpServices::CurvesToolkit => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CURVES_TOOLKIT,
             BlpServices::TechnicalAnalysis => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_TECHNICAL_ANALYSIS,
-            BlpServices::CorporateEarnings => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_TECHNICAL_ANALYSIS,
+            BlpServices::CorporateEarnings => BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CORPORATE_EARNINGS,
+            BlpServices::Custom(service) => service,
             BlpServices::NoService => "No-Service",
         }
     }
 }
 
+impl From<&str> for BlpServices {
+    fn from(arg: &str) -> Self {
+        match arg {
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MKTDATA => BlpServices::MarketData,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_REFDATA => BlpServices::ReferenceData,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_STATIC_MKT => BlpServices::StaticReferenceData,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_SOURCE_REF => BlpServices::SourceReference,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_VWAP => BlpServices::Vwap,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_DEPTH => BlpServices::MarketDepth,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_BAR => BlpServices::MarketBar,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_MARKET_LIST => BlpServices::MarketList,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_API_FIELDS => BlpServices::ApiFields,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_INSTRUMENTS => BlpServices::Instruments,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_PAGE_DATA => BlpServices::PageData,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_TECHNICAL_ANALYSIS => BlpServices::TechnicalAnalysis,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CURVES_TOOLKIT => BlpServices::CurvesToolkit,
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CORPORATE_EARNINGS => BlpServices::CorporateEarnings,
+            "" | "No-Service" => BlpServices::NoService,
+            service => BlpServices::Custom(service.to_string()),
+        }
+    }
+}
+
 /// A `Service`
 /// created from a `Session::get_service`
 #[derive(Debug)]
@@ -176,6 +214,107 @@ impl Service {
         unsafe { blpapi_Service_numEventDefinitions(self.ptr) as i64 }
     }
 
+    /// Get the operation by name
+    pub fn operation(&self, name: &str) -> Result<Operation, Error> {
+        let c_name = CString::new(name).unwrap_or_default();
+        let mut operation: *mut blpapi_Operation_t = ptr::null_mut();
+        let rc = unsafe {
+            blpapi_Service_getOperation(self.ptr, &mut operation, c_name.as_ptr(), ptr::null())
+        };
+        Error::check(rc)?;
+        Ok(Operation { ptr: operation })
+    }
+
+    /// Get the operation at index
+    pub fn operation_at(&self, index: usize) -> Result<Operation, Error> {
+        let mut operation: *mut blpapi_Operation_t = ptr::null_mut();
+        let rc = unsafe { blpapi_Service_getOperationAt(self.ptr, &mut operation, index) };
+        Error::check(rc)?;
+        Ok(Operation { ptr: operation })
+    }
+
+    /// Iterator over all operations of the service
+    pub fn operations(&self) -> Operations<'_> {
+        Operations {
+            service: self,
+            i: 0,
+            len: self.num_operations().max(0) as usize,
+        }
+    }
+
+    /// Get the event definition by name
+    pub fn event_definition(&self, name: &str) -> Result<SchemaElements, Error> {
+        let c_name = CString::new(name).unwrap_or_default();
+        let mut schema_ele: *mut blpapi_SchemaElementDefinition_t = ptr::null_mut();
+        let rc = unsafe {
+            blpapi_Service_getEventDefinition(
+                self.ptr,
+                &mut schema_ele,
+                c_name.as_ptr(),
+                ptr::null(),
+            )
+        };
+        Error::check(rc)?;
+        Ok(SchemaElements::default().from_ptr(schema_ele))
+    }
+
+    /// Get the event definition at index
+    pub fn event_definition_at(&self, index: usize) -> Result<SchemaElements, Error> {
+        let mut schema_ele: *mut blpapi_SchemaElementDefinition_t = ptr::null_mut();
+        let rc = unsafe { blpapi_Service_getEventDefinitionAt(self.ptr, &mut schema_ele, index) };
+        Error::check(rc)?;
+        Ok(SchemaElements::default().from_ptr(schema_ele))
+    }
+
+    /// Iterator over all event definitions of the service
+    pub fn event_definitions(&self) -> EventDefinitions<'_> {
+        EventDefinitions {
+            service: self,
+            i: 0,
+            len: self.num_event_definitions().max(0) as usize,
+        }
+    }
+
+    /// Serialize the full schema of the service as XML
+    pub fn serialize<T: Write>(&self, writer: &mut T) -> Result<(), Error> {
+        let mut context = StreamWriterContext { writer };
+        let res = unsafe {
+            blpapi_TestUtil_serializeService(
+                Some(write_to_stream_cb),
+                &mut context as *mut _ as *mut c_void,
+                self.ptr,
+            )
+        };
+        if res != 0 {
+            return Err(Error::struct_error(
+                "Service",
+                "serialize",
+                "Error when trying to write to stream writer",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Create a service from an XML schema written by `serialize`
+    pub fn deserialize(schema: &str) -> Result<Service, Error> {
+        let mut service_ptr: *mut blpapi_Service_t = ptr::null_mut();
+        let res = unsafe {
+            blpapi_TestUtil_deserializeService(
+                schema.as_ptr() as *const c_char,
+                schema.len(),
+                &mut service_ptr,
+            )
+        };
+        Error::check(res)?;
+        let mut service = Service {
+            ptr: service_ptr,
+            service: BlpServices::default(),
+            status: BlpServiceStatus::Active,
+        };
+        service.service = BlpServices::from(service.name().as_str());
+        Ok(service)
+    }
+
     /// release
     pub fn release(&self) -> Result<(), Error> {
         unsafe { blpapi_Service_release(self.ptr) };
@@ -217,3 +356,47 @@ impl Clone for Service {
         }
     }
 }
+
+/// An iterator over the operations of a service
+pub struct Operations<'a> {
+    service: &'a Service,
+    i: usize,
+    len: usize,
+}
+
+impl<'a> Iterator for Operations<'a> {
+    type Item = Operation;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.i, Some(self.len - self.i))
+    }
+    fn next(&mut self) -> Option<Operation> {
+        if self.i == self.len {
+            return None;
+        }
+        let v = self.service.operation_at(self.i).ok();
+        self.i += 1;
+        v
+    }
+}
+
+/// An iterator over the event definitions of a service
+pub struct EventDefinitions<'a> {
+    service: &'a Service,
+    i: usize,
+    len: usize,
+}
+
+impl<'a> Iterator for EventDefinitions<'a> {
+    type Item = SchemaElements;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.i, Some(self.len - self.i))
+    }
+    fn next(&mut self) -> Option<SchemaElements> {
+        if self.i == self.len {
+            return None;
+        }
+        let v = self.service.event_definition_at(self.i).ok();
+        self.i += 1;
+        v
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    abstract_session::AbstractSession,
+    abstract_session::{cancel_correlation_ids, AbstractSession},
+    connection::{ConnectedServer, ConnectionMonitor, ConnectionStatus},
     correlation_id::{CorrelationId, CorrelationIdBuilder},
     data_series::{
         CurveOptions, DataSeries, DataSeriesBuilder, FieldSeries, FieldSeriesBuilder, FieldTypes,
@@ -7,51 +8,66 @@ use crate::{
     },
     element::Element,
     event::{
-        Event, EventBuilder, EventQueue, EventType, SessionEvents, SubscriptionMsg,
-        SubscriptionStream,
+        Event, EventBuilder, EventQueue, EventType, SessionEvents, SlowConsumerPolicy,
+        SubscriptionDecoder, SubscriptionMsg, SubscriptionStream,
     },
     event_dispatcher::{EventDispatcher, EventDispatcherBuilder},
     identity::{Identity, IdentityBuilder, SeatType},
-    message::MessageStatus,
+    market_bar::{bar_subscription, MarketBar},
+    market_depth::{depth_subscription, BookType, OrderBook},
+    market_list::{list_subscription, MarketList, MarketListKind, MarketListUpdate},
     names::{
         BBG_ID, COUNTRY_CODE, CURRENCY_CODE, CURVE_ID, EVENT_TYPES, FIELDS_EXCLUDE, FIELDS_NAME,
         FIELDS_REQUEST_ID, FIELDS_SEARCH, FIELD_DATA, FIELD_DATA_ERROR, FIELD_EID_DATA, FIELD_ID,
         FIELD_TYPE, FIELD_TYPE_DOCS, LANGUAGE_OVERRIDE, MAX_RESULTS, OVERRIDES, PARTIAL_MATCH,
-        QUERY, RESULTS, SECURITIES, SECURITY, SECURITY_DATA, SECURITY_ERROR, SECURITY_NAME,
-        SECURITY_SUBTYPE, SECURITY_TYPE, TICKER, TICK_DATA, VALUE, YELLOW_KEY_FILTER,
+        QUERY, REASON, RESULTS, SECURITIES, SECURITY, SECURITY_DATA, SECURITY_ERROR, SECURITY_NAME,
+        SECURITY_SUBTYPE, SECURITY_TYPE, SERVICE_OPENED, SERVICE_OPEN_FAILURE, SESSION_STARTED,
+        SESSION_STARTUP_FAILURE, SESSION_TERMINATED, TICKER, TICK_DATA, VALUE, YELLOW_KEY_FILTER,
     },
     overrides::{BdpOptions, Override},
+    recording::{EventRecorder, EventReplay},
     ref_data::RefData,
     request::{Request, RequestTypes},
+    request_control::{Partial, PartialResult, RequestControl},
     service::{BlpServiceStatus, BlpServices, Service},
     session_options::SessionOptions,
     subscription_list::{
-        Subscription, SubscriptionList, SubscriptionListBuilder, SubscriptionRegistry, TickerInfo,
+        Subscription, SubscriptionKey, SubscriptionList, SubscriptionListBuilder,
+        SubscriptionRegistry, TickerInfo,
     },
     time_series::{
         DateType, HistIntradayOptions, HistOptions, IntradayDateType, TickData, TickDataBuilder,
         TickTypes, TimeSerieBuilder, TimeSeries,
     },
+    time_series_iter::{BdhIter, BdibIter},
+    vwap::VwapSubscription,
     Error,
 };
 use blpapi_sys::*;
-use std::{collections::HashMap, sync::mpsc};
+use chrono::NaiveTime;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{mpsc, Arc, Mutex},
+    time::{Duration, Instant},
+};
 use std::{
     ffi::{c_void, CString},
     ptr,
 };
 
-const MAX_PENDING_REQUEST: usize = 1024;
+pub(crate) const MAX_PENDING_REQUEST: usize = 1024;
 const MAX_REFDATA_FIELDS: usize = 400;
-const MAX_HISTDATA_FIELDS: usize = 25;
+pub(crate) const MAX_HISTDATA_FIELDS: usize = 25;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SubscriptionStatus {
     Unsubscribed,
     Subscribing,
     Subscribed,
     Cancelled,
     PendingCancellation,
+    /// Rejected by a `SubscriptionFailure`, see the reason of the subscription
+    Failed,
     Unknown,
 }
 
@@ -80,11 +96,44 @@ impl From<SubscriptionStatus> for u32 {
             SubscriptionStatus::PendingCancellation => {
                 BLPAPI_SUBSCRIPTIONSTATUS_PENDING_CANCELLATION
             }
+            // Failed subscriptions are unsubscribed in the SDK
+            SubscriptionStatus::Failed => BLPAPI_SUBSCRIPTIONSTATUS_UNSUBSCRIBED,
             SubscriptionStatus::Unknown => 113,
         }
     }
 }
 
+/// Completion of an asynchronous session operation
+#[derive(Debug)]
+pub enum SessionCompletion {
+    Started,
+    StartupFailure(Error),
+    /// Session stopped after `stop_async`
+    Terminated,
+    ServiceOpened(BlpServices),
+    ServiceOpenFailure {
+        service: BlpServices,
+        error: Error,
+    },
+}
+
+/// Asynchronous operations waiting for their status event
+#[derive(Debug, Default)]
+struct PendingOperations {
+    start: bool,
+    stop: bool,
+    services: HashMap<u64, BlpServices>,
+    completions: VecDeque<SessionCompletion>,
+    /// Events read by `wait_completions`, returned by the next reads of the session
+    events: VecDeque<Event>,
+}
+
+impl PendingOperations {
+    fn is_empty(&self) -> bool {
+        !self.start && !self.stop && self.services.is_empty()
+    }
+}
+
 #[allow(non_snake_case)]
 pub type EventHandler = Option<
     unsafe extern "C" fn(
@@ -94,6 +143,22 @@ pub type EventHandler = Option<
     ),
 >;
 
+/// Closure called by `SessionBuilder::on_event`
+pub type EventFn = dyn Fn(Event, &SessionRef) + Send + Sync;
+
+/// Closure set with `SessionBuilder::on_event`, passed to the C session as `userData`
+struct EventCallback {
+    handler: Box<EventFn>,
+    connection: ConnectionMonitor,
+    recorder: Option<EventRecorder>,
+}
+
+impl std::fmt::Debug for EventCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EventCallback")
+    }
+}
+
 /// SessionBuilder Struct to create Session
 #[derive(Default)]
 pub struct SessionBuilder {
@@ -101,6 +166,11 @@ pub struct SessionBuilder {
     pub dispatcher: Option<EventDispatcher>,
     pub time_out: Option<u32>,
     pub handler: EventHandler,
+    pub slow_consumer_policy: SlowConsumerPolicy,
+    pub recorder: Option<EventRecorder>,
+    pub replay: Option<EventReplay>,
+    pub validate_requests: bool,
+    callback: Option<Box<EventCallback>>,
 }
 
 impl SessionBuilder {
@@ -124,12 +194,57 @@ impl SessionBuilder {
         self
     }
 
+    /// Asynchronous session calling the closure for every event, takes precedence over `handler`
+    ///
+    /// # Note
+    /// The closure runs on the dispatcher threads, panics are caught and logged.
+    pub fn on_event<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Event, &SessionRef) + Send + Sync + 'static,
+    {
+        self.callback = Some(Box::new(EventCallback {
+            handler: Box::new(callback),
+            connection: ConnectionMonitor::default(),
+            recorder: None,
+        }));
+        self
+    }
+
+    /// Policy applied to subscription data while the consumer is slow
+    pub fn slow_consumer_policy(mut self, policy: SlowConsumerPolicy) -> Self {
+        self.slow_consumer_policy = policy;
+        self
+    }
+
+    /// Record every event the session receives
+    pub fn recorder(mut self, recorder: EventRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Validate each request against its schema before sending it
+    pub fn validate_requests(mut self, validate: bool) -> Self {
+        self.validate_requests = validate;
+        self
+    }
+
+    /// Feed the recorded events instead of connecting, the session does not need to be started
+    ///
+    /// # Note
+    /// Correlation ids are replayed as recorded, requests and subscriptions
+    /// have to be made in the same order as while recording.
+    pub fn replay(mut self, replay: EventReplay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
     fn sync_session(self, options: SessionOptions) -> Session {
         let handler = None;
         let time_out = self.time_out.unwrap_or_default();
         let dispatcher = EventDispatcherBuilder::default().build();
         let user_data = ptr::null_mut();
         let ptr = unsafe { blpapi_Session_create(options.ptr, handler, dispatcher.ptr, user_data) };
+        let connection = ConnectionMonitor::new(options.data.server_addresses.clone());
 
         Session {
             ptr,
@@ -142,17 +257,32 @@ impl SessionBuilder {
             correlation_count: 1,
             event_queue: true,
             registry: SubscriptionRegistry::default(),
-            registry_ticker_to_id: None,
+            registry_key_to_id: None,
+            slow_consumer_policy: self.slow_consumer_policy,
+            recorder: self.recorder,
+            replay: self.replay.map(|replay| Arc::new(Mutex::new(replay))),
+            validate_requests: self.validate_requests,
+            callback: None,
+            connection,
+            pending: PendingOperations::default(),
         }
     }
 
-    fn async_session(self, options: SessionOptions, handler: EventHandler) -> Session {
+    fn async_session(mut self, options: SessionOptions, handler: EventHandler) -> Session {
         let time_out = self.time_out.unwrap_or_default();
         let dispatcher = match self.dispatcher {
             Some(dp) => dp,
             None => EventDispatcherBuilder::default().build(),
         };
-        let user_data = ptr::null_mut();
+        let connection = ConnectionMonitor::new(options.data.server_addresses.clone());
+        if let Some(callback) = &mut self.callback {
+            callback.connection = connection.clone();
+            callback.recorder = self.recorder.clone();
+        }
+        let user_data = match &self.callback {
+            Some(callback) => &**callback as *const EventCallback as *mut c_void,
+            None => ptr::null_mut(),
+        };
         let ptr = unsafe { blpapi_Session_create(options.ptr, handler, dispatcher.ptr, user_data) };
 
         Session {
@@ -166,15 +296,23 @@ impl SessionBuilder {
             correlation_count: 1,
             event_queue: false,
             registry: SubscriptionRegistry::default(),
-            registry_ticker_to_id: None,
+            registry_key_to_id: None,
+            slow_consumer_policy: self.slow_consumer_policy,
+            recorder: self.recorder,
+            replay: self.replay.map(|replay| Arc::new(Mutex::new(replay))),
+            validate_requests: self.validate_requests,
+            callback: self.callback,
+            connection,
+            pending: PendingOperations::default(),
         }
     }
 
     pub fn build(self) -> Session {
         let opt = self.options.clone().unwrap_or_default();
-        match self.handler {
-            Some(handler) => self.async_session(opt, Some(handler)),
-            None => self.sync_session(opt),
+        match (&self.callback, self.handler) {
+            (Some(_), _) => self.async_session(opt, Some(event_callback)),
+            (None, Some(handler)) => self.async_session(opt, Some(handler)),
+            (None, None) => self.sync_session(opt),
         }
     }
 }
@@ -191,7 +329,16 @@ pub struct Session {
     pub time_out: u32,
     pub event_queue: bool,
     pub registry: SubscriptionRegistry,
-    pub registry_ticker_to_id: Option<HashMap<String, CorrelationId>>,
+    pub registry_key_to_id: Option<HashMap<SubscriptionKey, CorrelationId>>,
+    pub slow_consumer_policy: SlowConsumerPolicy,
+    pub recorder: Option<EventRecorder>,
+    pub replay: Option<Arc<Mutex<EventReplay>>>,
+    /// Whether requests are validated against their schema before sending
+    pub validate_requests: bool,
+    /// Connection state, updated from the `SessionStatus` events the session reads
+    pub connection: ConnectionMonitor,
+    callback: Option<Box<EventCallback>>,
+    pending: PendingOperations,
 }
 
 impl AbstractSession for Session {
@@ -232,6 +379,11 @@ impl Session {
 
     /// Open service
     pub fn open_service(&mut self, service: &BlpServices) -> Result<&mut Self, Error> {
+        if self.replay.is_some() {
+            self.get_service(service)?;
+            self.open_services.push(service.clone());
+            return Ok(self);
+        }
         let service_str: &str = service.into();
         let c_service = CString::new(service_str).unwrap_or_default();
         let res = match self.async_ {
@@ -241,17 +393,21 @@ impl Session {
             },
             false => unsafe { blpapi_Session_openService(self.ptr, c_service.as_ptr()) },
         } as i32;
-        match res == 0 {
-            true => {
-                self.open_services.push(service.clone());
-                Ok(self)
-            }
-            false => Err(Error::Session),
+        if res != 0 {
+            return Err(Error::Session);
+        }
+        if let (Some(recorder), false) = (&self.recorder, self.async_) {
+            recorder.record_service(&self.get_service(service)?)?;
         }
+        self.open_services.push(service.clone());
+        Ok(self)
     }
 
     /// Start the session
     pub fn start(&mut self) -> Result<(), Error> {
+        if self.replay.is_some() {
+            return Ok(());
+        }
         let res = match self.async_ {
             true => {
                 self.dispatcher.start()?;
@@ -267,6 +423,9 @@ impl Session {
 
     /// Stop the session
     pub fn stop(&mut self) -> Result<(), Error> {
+        if self.replay.is_some() {
+            return Ok(());
+        }
         let res = match self.async_ {
             true => {
                 self.dispatcher.stop(&true)?;
@@ -280,9 +439,175 @@ impl Session {
         }
     }
 
+    /// Start the session without waiting, completes with `SessionCompletion::Started`
+    ///
+    /// # Note
+    /// Sessions with a handler receive the `SessionStatus` events in the handler instead.
+    pub fn start_async(&mut self) -> Result<(), Error> {
+        if self.replay.is_some() {
+            self.pending
+                .completions
+                .push_back(SessionCompletion::Started);
+            return Ok(());
+        }
+        if self.async_ {
+            self.dispatcher.start()?;
+        }
+        Error::check(unsafe { blpapi_Session_startAsync(self.ptr) })?;
+        self.pending.start = !self.async_;
+        Ok(())
+    }
+
+    /// Stop the session without waiting, completes with `SessionCompletion::Terminated`
+    pub fn stop_async(&mut self) -> Result<(), Error> {
+        if self.replay.is_some() {
+            self.pending
+                .completions
+                .push_back(SessionCompletion::Terminated);
+            return Ok(());
+        }
+        Error::check(unsafe { blpapi_Session_stopAsync(self.ptr) })?;
+        self.pending.stop = !self.async_;
+        Ok(())
+    }
+
+    /// Open the service without waiting, completes with `SessionCompletion::ServiceOpened`
+    pub fn open_service_async(&mut self, service: &BlpServices) -> Result<CorrelationId, Error> {
+        let mut id = self.new_correlation_id();
+        if self.replay.is_some() {
+            self.open_service(service)?;
+            let completion = SessionCompletion::ServiceOpened(service.clone());
+            self.pending.completions.push_back(completion);
+            return Ok(id);
+        }
+        let service_str: &str = service.into();
+        let c_service = CString::new(service_str).unwrap_or_default();
+        let res =
+            unsafe { blpapi_Session_openServiceAsync(self.ptr, c_service.as_ptr(), &mut id.id) };
+        Error::check(res)?;
+        if !self.async_ {
+            self.pending.services.insert(id.value, service.clone());
+        }
+        Ok(id)
+    }
+
+    /// Open all services in parallel, see `open_service_async`
+    pub fn open_services_async(
+        &mut self,
+        services: &[BlpServices],
+    ) -> Result<Vec<CorrelationId>, Error> {
+        services
+            .iter()
+            .map(|service| self.open_service_async(service))
+            .collect()
+    }
+
+    /// Whether asynchronous operations are still waiting for their status event
+    ///
+    /// # Note
+    /// Sessions with a handler receive the status events in the handler,
+    /// their operations are never pending.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Completed asynchronous operations of the events read so far
+    pub fn completions(&mut self) -> Vec<SessionCompletion> {
+        self.pending.completions.drain(..).collect()
+    }
+
+    /// Read events until all asynchronous operations completed or the timeout elapsed
+    ///
+    /// # Note
+    /// Events read while waiting are returned by the next `next_event` or `try_next_event`,
+    /// operations still pending after the timeout stay pending.
+    pub fn wait_completions(&mut self, timeout: Duration) -> Result<Vec<SessionCompletion>, Error> {
+        if self.async_ {
+            return Err(Error::struct_error(
+                "Session",
+                "wait_completions",
+                "Events are delivered to the session handler",
+            ));
+        }
+        let deadline = Instant::now() + timeout;
+        while self.is_pending() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            let ms = remaining.as_millis().clamp(1, u32::MAX as u128) as u32;
+            let event = self.read_event(ms)?;
+            if event.event_type != EventType::Timeout {
+                self.pending.events.push_back(event);
+            }
+        }
+        Ok(self.completions())
+    }
+
+    /// Complete the pending operations of the status event
+    fn process_completions(&mut self, event: &Event) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let event_type = event.event_type;
+        for message in event.messages() {
+            let m_type = message.message_type();
+            let reason = || {
+                let element = message.element();
+                Error::request_failure(element.get_named_element(&REASON).unwrap_or(element))
+            };
+            let completion = match event_type {
+                EventType::SessionStatus if self.pending.start && m_type == *SESSION_STARTED => {
+                    self.pending.start = false;
+                    SessionCompletion::Started
+                }
+                EventType::SessionStatus
+                    if self.pending.start && m_type == *SESSION_STARTUP_FAILURE =>
+                {
+                    self.pending.start = false;
+                    SessionCompletion::StartupFailure(reason())
+                }
+                EventType::SessionStatus if self.pending.stop && m_type == *SESSION_TERMINATED => {
+                    self.pending.stop = false;
+                    SessionCompletion::Terminated
+                }
+                EventType::ServiceStatus => {
+                    let service = message
+                        .correlation_id(0)
+                        .and_then(|cid| self.pending.services.remove(&cid.value));
+                    match service {
+                        Some(service) if m_type == *SERVICE_OPENED => {
+                            if !self.open_services.contains(&service) {
+                                self.open_services.push(service.clone());
+                            }
+                            SessionCompletion::ServiceOpened(service)
+                        }
+                        Some(service) if m_type == *SERVICE_OPEN_FAILURE => {
+                            SessionCompletion::ServiceOpenFailure {
+                                service,
+                                error: reason(),
+                            }
+                        }
+                        _ => continue,
+                    }
+                }
+                _ => continue,
+            };
+            self.pending.completions.push_back(completion);
+        }
+    }
+
     /// Get opened service
     pub fn get_service(&self, service: &BlpServices) -> Result<Service, Error> {
         let blp_serv: &str = service.into();
+        if let Some(replay) = &self.replay {
+            return replay
+                .lock()
+                .unwrap()
+                .service(blp_serv)
+                .cloned()
+                .ok_or(Error::Service);
+        }
         let name = CString::new(blp_serv).unwrap();
         let mut service_ptr = ptr::null_mut();
         let res = unsafe {
@@ -345,6 +670,16 @@ impl Session {
         request: Request,
         correlation_id: &mut CorrelationId,
     ) -> Result<SessionEvents<'_>, Error> {
+        let event_queue = self.send_queued(request, correlation_id)?;
+        Ok(SessionEvents::new(self, *correlation_id, event_queue))
+    }
+
+    /// Send request, returning the queue its events are delivered to
+    fn send_queued(
+        &mut self,
+        request: Request,
+        correlation_id: &mut CorrelationId,
+    ) -> Result<EventQueue, Error> {
         let identity = ptr::null_mut();
         let event_queue = match self.event_queue {
             true => EventQueue::new(self.time_out as i32),
@@ -352,6 +687,12 @@ impl Session {
         };
         let request_label = ptr::null_mut();
         let request_label_len = 0;
+        if self.validate_requests {
+            request.validate()?;
+        }
+        if self.replay.is_some() {
+            return Ok(event_queue);
+        }
         unsafe {
             let res = blpapi_Session_sendRequest(
                 self.ptr,
@@ -364,34 +705,94 @@ impl Session {
             );
             Error::check(res)?;
         }
-        Ok(SessionEvents::new(self, *correlation_id, event_queue))
+        Ok(event_queue)
     }
 
     /// Request for next event, optionally waiting timeout_ms if there is no event
     pub fn next_event(&mut self) -> Result<Event, Error> {
+        self.next_event_within(self.time_out)
+    }
+
+    fn next_event_within(&mut self, time_out: u32) -> Result<Event, Error> {
+        match self.pending.events.pop_front() {
+            Some(event) => Ok(event),
+            None => self.read_event(time_out),
+        }
+    }
+
+    /// Read the next event of the session, updating the connection and the completions
+    fn read_event(&mut self, time_out: u32) -> Result<Event, Error> {
+        if let Some(replay) = &self.replay {
+            return next_replay_event(replay);
+        }
         let mut event: *mut blpapi_Event_t = ptr::null_mut();
-        unsafe {
-            let res = blpapi_Session_nextEvent(self.ptr, &mut event as *mut _, self.time_out);
+        let event = unsafe {
+            let res = blpapi_Session_nextEvent(self.ptr, &mut event as *mut _, time_out);
             Error::check(res)?;
-            let event = EventBuilder::default().ptr(event).build();
-            Ok(event)
-        }
+            EventBuilder::default().ptr(event).build()
+        };
+        self.record(&event);
+        self.connection.process_event(&event);
+        self.process_completions(&event);
+        Ok(event)
     }
 
     /// Request for try-next event, if there is no event continue
     pub fn try_next_event(&mut self) -> Option<Event> {
+        if let Some(event) = self.pending.events.pop_front() {
+            return Some(event);
+        }
+        if let Some(replay) = &self.replay {
+            return match replay.lock().unwrap().next_event()? {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    log::warn!("Replaying event failed: {}", e);
+                    None
+                }
+            };
+        }
         let mut event: *mut blpapi_Event_t = ptr::null_mut();
-        unsafe {
+        let event = unsafe {
             let res = blpapi_Session_tryNextEvent(self.ptr, &mut event);
 
             match res == 0 {
-                true => Some(EventBuilder::default().ptr(event).build()),
-                false => None,
+                true => EventBuilder::default().ptr(event).build(),
+                false => return None,
+            }
+        };
+        self.record(&event);
+        self.connection.process_event(&event);
+        self.process_completions(&event);
+        Some(event)
+    }
+
+    /// Next event of a request, read from the request queue if the session uses one
+    pub(crate) fn next_request_event(
+        &mut self,
+        queue: &mut EventQueue,
+        time_out: u32,
+    ) -> Result<Event, Error> {
+        if self.replay.is_some() || !self.event_queue {
+            return self.next_event_within(time_out);
+        }
+        let event = queue.timeout(time_out as i32).next_event()?;
+        self.record(&event);
+        Ok(event)
+    }
+
+    /// Record the event if recording, a failing recorder does not drop the event
+    fn record(&self, event: &Event) {
+        if let Some(recorder) = &self.recorder {
+            if let Err(e) = recorder.record(event) {
+                log::warn!("Recording event failed: {}", e);
             }
         }
     }
 
     fn session_subscribe(&mut self, subscription_list: &SubscriptionList) -> Result<(), Error> {
+        if self.replay.is_some() {
+            return Ok(());
+        }
         let sub_service = &subscription_list.service;
         let open_service = self.open_services.iter().find(|s| *s == sub_service);
         let _service = match open_service {
@@ -425,6 +826,9 @@ impl Session {
     }
 
     fn session_resubscribe(&mut self, subscription_list: &SubscriptionList) -> Result<(), Error> {
+        if self.replay.is_some() {
+            return Ok(());
+        }
         let request_label = ptr::null_mut();
         let request_label_len = 0;
         let res = unsafe {
@@ -443,8 +847,32 @@ impl Session {
     where
         R: RefData + Send + 'static,
     {
-        let (tx, rx) = mpsc::channel();
         let stream = SubscriptionStream::<R>::new(self.ptr, self.registry.clone());
+        self.spawn_stream(stream)
+    }
+
+    /// Start a subscription stream decoding the data with the given decoder
+    pub fn start_subscription_with<R>(
+        &self,
+        decoder: SubscriptionDecoder<R>,
+    ) -> mpsc::Receiver<SubscriptionMsg<R>>
+    where
+        R: Send + 'static,
+    {
+        let stream = SubscriptionStream::with_decoder(self.ptr, self.registry.clone(), decoder);
+        self.spawn_stream(stream)
+    }
+
+    fn spawn_stream<R>(&self, stream: SubscriptionStream<R>) -> mpsc::Receiver<SubscriptionMsg<R>>
+    where
+        R: Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        let stream = stream
+            .policy(self.slow_consumer_policy)
+            .recorder(self.recorder.clone())
+            .replay(self.replay.clone())
+            .connection(self.connection.clone());
 
         std::thread::spawn(move || {
             for msg in stream.flatten() {
@@ -457,74 +885,327 @@ impl Session {
         rx
     }
 
+    /// Current connection status, e.g. for health checks
+    pub fn connection_status(&self) -> ConnectionStatus {
+        self.connection.status()
+    }
+
+    /// Server address the session is connected to, `None` while disconnected
+    pub fn server_address(&self) -> Option<ConnectedServer> {
+        self.connection.server()
+    }
+
+    /// Number of messages dropped for the subscription since subscribing
+    pub fn data_loss(&self, key: &SubscriptionKey) -> Option<u64> {
+        self.ticker_info(key).map(|info| info.data_loss)
+    }
+
+    /// Last known status of the subscription
+    pub fn subscription_status(&self, key: &SubscriptionKey) -> Option<SubscriptionStatus> {
+        self.ticker_info(key).map(|info| info.status)
+    }
+
+    /// Registry entry of the subscription
+    pub fn ticker_info(&self, key: &SubscriptionKey) -> Option<TickerInfo> {
+        let correlation_id = self.registry_key_to_id.as_ref()?.get(key)?;
+        let reg = self.registry.lock().unwrap();
+        reg.get(&correlation_id.value).cloned()
+    }
+
+    /// Key of the subscription, resolving the default topic prefix of the session
+    pub fn subscription_key(&self, sub: &Subscription) -> SubscriptionKey {
+        SubscriptionKey {
+            service: sub.service.clone(),
+            topic_prefix: self.topic_prefix(sub),
+            ticker: sub.ticker.clone(),
+        }
+    }
+
+    /// Subscribe to the tickers on the service and topic prefix of each subscription
     #[inline(always)]
     pub fn subscribe<R>(&mut self, sub_vec: Vec<Subscription>) -> Result<(), Error>
     where
         R: RefData + std::fmt::Debug,
     {
-        let service = BlpServices::MarketData;
-        let sub_list = SubscriptionListBuilder::default().service(service);
+        self.subscribe_all(sub_vec)
+    }
+
+    /// Subscribe to the order books of the tickers on `//blp/mktdepthdata` (B-Pipe only)
+    pub fn subscribe_market_depth(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl Into<String>>,
+        book_type: BookType,
+    ) -> Result<(), Error> {
+        let sub_vec = tickers
+            .into_iter()
+            .map(|ticker| depth_subscription(ticker, book_type))
+            .collect();
+        self.subscribe_all(sub_vec)
+    }
+
+    /// Start a stream of order book snapshots for market depth subscriptions
+    pub fn start_market_depth(&self) -> mpsc::Receiver<SubscriptionMsg<OrderBook>> {
+        self.start_subscription_with(OrderBook::decoder())
+    }
+
+    /// Subscribe to OHLCV bars of the tickers on `//blp/mktbar`
+    /// with the interval in minutes and optional GMT start and end time
+    pub fn subscribe_bars(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl Into<String>>,
+        interval: u32,
+        start_time: Option<NaiveTime>,
+        end_time: Option<NaiveTime>,
+    ) -> Result<(), Error> {
+        let sub_vec = tickers
+            .into_iter()
+            .map(|ticker| bar_subscription(ticker, interval, start_time, end_time))
+            .collect();
+        self.subscribe_all(sub_vec)
+    }
+
+    /// Start a stream of market bars for bar subscriptions
+    pub fn start_bars(&self) -> mpsc::Receiver<SubscriptionMsg<MarketBar>> {
+        self.start_subscription_with(MarketBar::decoder())
+    }
+
+    /// Subscribe to chains or security id lists on `//blp/mktlist`
+    pub fn subscribe_market_list(
+        &mut self,
+        lists: impl IntoIterator<Item = impl Into<String>>,
+        kind: MarketListKind,
+    ) -> Result<(), Error> {
+        let sub_vec = lists
+            .into_iter()
+            .map(|list| list_subscription(list, kind))
+            .collect();
+        self.subscribe_all(sub_vec)
+    }
+
+    /// Start a stream of member changes for market list subscriptions
+    pub fn start_market_list(&self) -> mpsc::Receiver<SubscriptionMsg<MarketListUpdate>> {
+        self.start_subscription_with(MarketList::decoder())
+    }
 
-        let mut sub_list = sub_list.build();
-        let mut ticker_hm: HashMap<String, CorrelationId> = HashMap::new();
+    /// Subscribe to VWAP values on `//blp/mktvwap`
+    /// Use `start_subscription::<Vwap>()` to receive the values
+    pub fn subscribe_vwap(&mut self, vwap_vec: &[VwapSubscription]) -> Result<(), Error> {
+        let sub_vec = vwap_vec.iter().map(|vwap| vwap.build()).collect();
+        self.subscribe_all(sub_vec)
+    }
 
+    /// Topic prefix of the subscription, the default topic prefix of the session options if not set
+    fn topic_prefix(&self, sub: &Subscription) -> String {
+        let prefix = sub
+            .topic_prefix
+            .as_deref()
+            .unwrap_or(&self.options.data.topic_prefix)
+            .trim_matches('/');
+        match prefix.is_empty() {
+            true => String::from("/"),
+            false => format!("/{}/", prefix),
+        }
+    }
+
+    /// Register all tickers and subscribe with one subscription list per service
+    fn subscribe_all(&mut self, sub_vec: Vec<Subscription>) -> Result<(), Error> {
+        let mut by_service: Vec<(BlpServices, Vec<Subscription>)> = vec![];
         for sub in sub_vec {
-            let correlation_id = self.new_correlation_id();
-            let info = TickerInfo {
-                ticker: sub.ticker.clone(),
-                requested_fields: sub
+            match by_service.iter_mut().find(|(s, _)| *s == sub.service) {
+                Some((_, subs)) => subs.push(sub),
+                None => by_service.push((sub.service.clone(), vec![sub])),
+            }
+        }
+
+        for (service, subs) in by_service {
+            let mut sub_list = SubscriptionListBuilder::default()
+                .service(service.clone())
+                .build();
+            let mut key_hm: HashMap<SubscriptionKey, CorrelationId> = HashMap::new();
+
+            for sub in subs {
+                let correlation_id = self.new_correlation_id();
+                let topic_prefix = self.topic_prefix(&sub);
+                let fields: Vec<String> = sub
                     .fields
                     .iter()
                     .map(|&s| s.to_string().to_uppercase())
-                    .collect(),
-            };
-            ticker_hm.insert(sub.ticker.clone(), correlation_id);
-
-            self.registry
-                .lock()
-                .unwrap()
-                .insert(correlation_id.value, info);
-            sub_list.add(sub.ticker, correlation_id, Some(sub.fields), sub.options)?;
+                    .collect();
+                let info = TickerInfo {
+                    ticker: sub.ticker.clone(),
+                    requested_fields: fields.iter().cloned().collect(),
+                    service: service.clone(),
+                    topic_prefix: topic_prefix.clone(),
+                    fields,
+                    options: sub.options.clone(),
+                    data_loss: 0,
+                    status: SubscriptionStatus::Subscribing,
+                    reason: None,
+                    streams: vec![],
+                };
+                key_hm.insert(info.key(), correlation_id);
+
+                self.registry
+                    .lock()
+                    .unwrap()
+                    .insert(correlation_id.value, info);
+                sub_list.add_with_prefix(
+                    &topic_prefix,
+                    sub.ticker,
+                    correlation_id,
+                    Some(sub.fields),
+                    sub.options,
+                )?;
+            }
+            self.registry_key_to_id
+                .get_or_insert_with(HashMap::new)
+                .extend(key_hm);
+            self.session_subscribe(&sub_list)?;
         }
-        self.registry_ticker_to_id = Some(ticker_hm);
-        self.session_subscribe(&sub_list)?;
         Ok(())
     }
 
+    /// Change fields and options of existing subscriptions
+    /// Subscriptions are matched by their service, topic prefix and ticker
     #[inline(always)]
     pub fn resubscribe<R>(&mut self, sub_vec: Vec<Subscription>) -> Result<(), Error>
     where
         R: RefData + std::fmt::Debug,
     {
-        let service = BlpServices::MarketData;
-        let sub_list = SubscriptionListBuilder::default().service(service);
-        let mut sub_list = sub_list.build();
+        let reg_hashm = self
+            .registry_key_to_id
+            .clone()
+            .expect("Expect existing Registry. Start a subscribtion first.");
 
+        let mut sub_lists: Vec<SubscriptionList> = vec![];
         for sub in sub_vec {
-            let reg_hashm = self
-                .registry_ticker_to_id
-                .clone()
-                .expect("Expect existing Registry. Start a subscribtion first.");
-
             let correlation_id =
                 reg_hashm
-                    .get(&sub.ticker)
+                    .get(&self.subscription_key(&sub))
                     .ok_or(Error::NotFound(String::from(
-                        "No Cid found for this Ticker.",
+                        "No Cid found for this Subscription.",
                     )))?;
-            if let Some(info) = self.registry.lock().unwrap().get_mut(&correlation_id.value) {
-                info.requested_fields = sub
+            let (service, topic_prefix) = {
+                let mut reg = self.registry.lock().unwrap();
+                let info =
+                    reg.get_mut(&correlation_id.value)
+                        .ok_or(Error::NotFound(String::from(
+                            "No subscription found for this Ticker.",
+                        )))?;
+                info.fields = sub
                     .fields
                     .iter()
                     .map(|&s| s.to_owned().to_uppercase())
                     .collect();
+                info.requested_fields = info.fields.iter().cloned().collect();
+                info.options = sub.options.clone();
+                (info.service.clone(), info.topic_prefix.clone())
+            };
+
+            let index = match sub_lists.iter().position(|list| list.service == service) {
+                Some(index) => index,
+                None => {
+                    sub_lists.push(SubscriptionListBuilder::default().service(service).build());
+                    sub_lists.len() - 1
+                }
             };
-            sub_list.add(sub.ticker, *correlation_id, Some(sub.fields), sub.options)?;
+            sub_lists[index].add_with_prefix(
+                &topic_prefix,
+                sub.ticker,
+                *correlation_id,
+                Some(sub.fields),
+                sub.options,
+            )?;
+        }
+        for sub_list in &sub_lists {
+            self.session_resubscribe(sub_list)?;
+        }
+        Ok(())
+    }
+
+    /// Restart a terminated session
+    /// Creates a new session with the same options, reopens all services
+    /// and replays every subscription of the registry.
+    /// Returns the number of replayed subscriptions
+    ///
+    /// # Note
+    /// The old session is destroyed, only the supervisor calls this once its stream stopped.
+    pub(crate) fn restart(&mut self) -> Result<usize, Error> {
+        if self.async_ {
+            return Err(Error::struct_error(
+                "Session",
+                "restart",
+                "Restart is only supported for synchronous sessions",
+            ));
+        }
+        // Services belong to the old session and have to be released first
+        self.act_services.clear();
+        let services = std::mem::take(&mut self.open_services);
+        unsafe { blpapi_Session_destroy(self.ptr) };
+
+        let handler = None;
+        let user_data = ptr::null_mut();
+        self.ptr = unsafe {
+            blpapi_Session_create(self.options.ptr, handler, self.dispatcher.ptr, user_data)
+        };
+        if let Err(e) = self.reopen_services(&services) {
+            // Keep the services for the next attempt
+            self.open_services = services;
+            return Err(e);
+        }
+        self.replay_subscriptions()
+    }
+
+    fn reopen_services(&mut self, services: &[BlpServices]) -> Result<(), Error> {
+        self.start()?;
+        for service in services {
+            self.open_service(service)?;
+            let new_service = self.get_service(service)?;
+            let service: &str = service.into();
+            self.act_services.insert(service.to_string(), new_service);
         }
-        self.session_resubscribe(&sub_list)?;
         Ok(())
     }
 
+    /// Subscribe again to all entries of the registry
+    /// with their original correlation ids, fields and options
+    pub fn replay_subscriptions(&mut self) -> Result<usize, Error> {
+        let entries: Vec<(u64, TickerInfo)> = self
+            .registry
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(cid, info)| (*cid, info.clone()))
+            .collect();
+
+        let mut by_service: Vec<(BlpServices, Vec<(u64, TickerInfo)>)> = vec![];
+        for (cid, info) in entries {
+            match by_service.iter_mut().find(|(s, _)| *s == info.service) {
+                Some((_, infos)) => infos.push((cid, info)),
+                None => by_service.push((info.service.clone(), vec![(cid, info)])),
+            }
+        }
+
+        let mut replayed = 0;
+        for (service, infos) in by_service {
+            let mut sub_list = SubscriptionListBuilder::default().service(service).build();
+            for (cid, info) in infos {
+                let fields: Vec<&str> = info.fields.iter().map(|f| f.as_str()).collect();
+                let correlation_id = CorrelationId::new_u64(cid);
+                sub_list.add_with_prefix(
+                    &info.topic_prefix,
+                    info.ticker,
+                    correlation_id,
+                    Some(fields),
+                    info.options,
+                )?;
+                replayed += 1;
+            }
+            self.session_subscribe(&sub_list)?;
+        }
+        Ok(replayed)
+    }
+
     /// Get reference data for `RefData` items
     ///
     /// # Note
@@ -543,8 +1224,50 @@ impl Session {
     where
         R: RefData,
     {
-        // let mut ref_data: HashMap<String, R> = HashMap::new();
+        let control = RequestControl::default();
+        self.bdp_with(tickers, overrides, static_mkt, options, &control)
+            .map_err(Error::from)
+    }
+
+    /// Get reference data, stopping at the deadline or cancellation of the control
+    ///
+    /// # Note
+    /// A stopped request is cancelled, the data received so far is returned with `Error::TimeOut`.
+    pub fn bdp_with<R>(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl AsRef<str>>,
+        overrides: Option<&Vec<Override>>,
+        static_mkt: bool,
+        options: Option<BdpOptions>,
+        control: &RequestControl,
+    ) -> PartialResult<Vec<DataSeries<R>>>
+    where
+        R: RefData,
+    {
         let mut ref_data: Vec<DataSeries<R>> = vec![];
+        let res = self.bdp_into(
+            tickers,
+            overrides,
+            static_mkt,
+            options,
+            control,
+            &mut ref_data,
+        );
+        partial_result(res, ref_data)
+    }
+
+    fn bdp_into<R>(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl AsRef<str>>,
+        overrides: Option<&Vec<Override>>,
+        static_mkt: bool,
+        options: Option<BdpOptions>,
+        control: &RequestControl,
+        ref_data: &mut Vec<DataSeries<R>>,
+    ) -> Result<(), Error>
+    where
+        R: RefData,
+    {
         let mut iter = tickers.into_iter();
 
         // split request as necessary to comply with bloomberg size limitations
@@ -589,16 +1312,16 @@ impl Session {
                     }
                 }
 
-                // for event in self.send(request, &correlation_id)? {
                 let mut correlation_id = self.new_correlation_id();
-                for event in self.send(request, &mut correlation_id)? {
+                let events = self.send(request, &mut correlation_id)?;
+                for event in events.control(control.clone()) {
                     for message in event?.messages() {
-                        process_message(message.element(), &mut ref_data)?;
+                        process_message(message.element(), ref_data)?;
                     }
                 }
             }
         }
-        Ok(ref_data)
+        Ok(())
     }
 
     /// Get reference data for `HistoricalData` items
@@ -614,11 +1337,61 @@ impl Session {
         tickers: impl IntoIterator<Item = impl AsRef<str>>,
         options: HistOptions,
     ) -> Result<Vec<TimeSeries<R, DateType>>, Error>
+    where
+        R: RefData,
+    {
+        let control = RequestControl::default();
+        self.bdh_with(tickers, options, &control)
+            .map_err(Error::from)
+    }
+
+    /// Get historical data row by row, see `BdhIter`
+    ///
+    /// # Note
+    /// Rows are decoded as each partial response arrives instead of collecting all of them.
+    pub fn bdh_iter<R>(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl AsRef<str>>,
+        options: HistOptions,
+    ) -> BdhIter<'_, R>
+    where
+        R: RefData,
+    {
+        let tickers = tickers
+            .into_iter()
+            .map(|t| t.as_ref().to_string())
+            .collect();
+        BdhIter::new(self, tickers, options)
+    }
+
+    /// Get historical data, stopping at the deadline or cancellation of the control
+    ///
+    /// # Note
+    /// A stopped request is cancelled, the data received so far is returned with `Error::TimeOut`.
+    pub fn bdh_with<R>(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl AsRef<str>>,
+        options: HistOptions,
+        control: &RequestControl,
+    ) -> PartialResult<Vec<TimeSeries<R, DateType>>>
     where
         R: RefData,
     {
         let mut ref_data: Vec<TimeSeries<R, DateType>> = vec![];
+        let res = self.bdh_into(tickers, options, control, &mut ref_data);
+        partial_result(res, ref_data)
+    }
 
+    fn bdh_into<R>(
+        &mut self,
+        tickers: impl IntoIterator<Item = impl AsRef<str>>,
+        options: HistOptions,
+        control: &RequestControl,
+        ref_data: &mut Vec<TimeSeries<R, DateType>>,
+    ) -> Result<(), Error>
+    where
+        R: RefData,
+    {
         let mut iter = tickers.into_iter();
 
         // split request as necessary to comply with bloomberg size limitations
@@ -647,14 +1420,15 @@ impl Session {
                 }
 
                 let mut correlation_id = self.new_correlation_id();
-                for event in self.send(request, &mut correlation_id)? {
+                let events = self.send(request, &mut correlation_id)?;
+                for event in events.control(control.clone()) {
                     for message in event?.messages() {
-                        process_message_ts(&mut message.element(), &mut ref_data)?;
+                        process_message_ts(&mut message.element(), ref_data)?;
                     }
                 }
             }
         }
-        Ok(ref_data)
+        Ok(())
     }
 
     /// Get reference data for `HistoricalData` tick items
@@ -671,52 +1445,136 @@ impl Session {
         tick_types: Vec<TickTypes>,
         options: HistIntradayOptions,
     ) -> Result<Vec<TimeSeries<TickData, IntradayDateType>>, Error> {
-        let mut ref_data: Vec<TimeSeries<TickData, IntradayDateType>> = vec![];
+        let control = RequestControl::default();
+        self.bdib_with(ticker, tick_types, options, &control)
+            .map_err(Error::from)
+    }
 
-        // split request as necessary to comply with bloomberg size limitations
-        loop {
-            let mut is_over = true;
-            // add next batch of securities and exit loop if empty
-            let service = BlpServices::ReferenceData;
-            let req_t = RequestTypes::IntradayTick;
-            let mut request = self.create_request(service, req_t)?;
+    /// Get intraday tick data tick by tick, paging through the window, see `BdibIter`
+    pub fn bdib_iter(
+        &mut self,
+        ticker: String,
+        tick_types: Vec<TickTypes>,
+        options: HistIntradayOptions,
+    ) -> BdibIter<'_> {
+        BdibIter::new(self, ticker, tick_types, options)
+    }
 
-            request.element().set_named(&SECURITY, ticker.as_ref())?;
+    /// Get intraday tick data, stopping at the deadline or cancellation of the control
+    ///
+    /// # Note
+    /// A stopped request is cancelled, the data received so far is returned with `Error::TimeOut`.
+    pub fn bdib_with(
+        &mut self,
+        ticker: String,
+        tick_types: Vec<TickTypes>,
+        options: HistIntradayOptions,
+        control: &RequestControl,
+    ) -> PartialResult<Vec<TimeSeries<TickData, IntradayDateType>>> {
+        let mut ref_data: Vec<TimeSeries<TickData, IntradayDateType>> = vec![];
+        let res = self.bdib_into(ticker, tick_types, options, control, &mut ref_data);
+        partial_result(res, ref_data)
+    }
 
-            options.apply(&mut request)?;
+    fn bdib_into(
+        &mut self,
+        ticker: String,
+        tick_types: Vec<TickTypes>,
+        options: HistIntradayOptions,
+        control: &RequestControl,
+        ref_data: &mut Vec<TimeSeries<TickData, IntradayDateType>>,
+    ) -> Result<(), Error> {
+        // split the range as configured to comply with bloomberg tick limitations
+        let windows = options.windows();
+        let last = windows.len() - 1;
+        // requests only run in parallel with their own event queue
+        let parallel = match self.event_queue && self.replay.is_none() {
+            true => options.parallel_requests(),
+            false => 1,
+        };
 
-            for field in tick_types.iter() {
-                let tick_type: &str = field.into();
-                request.append_named(&EVENT_TYPES, tick_type.as_ref())?;
+        for (batch, windows) in windows.chunks(parallel).enumerate() {
+            let mut pending = Vec::with_capacity(windows.len());
+            for (index, window) in windows.iter().enumerate() {
+                // ticks at the end of a window are part of the next one
+                let cutoff = match batch * parallel + index == last {
+                    true => None,
+                    false => window.range().map(|(_, end)| end),
+                };
+                match self.send_window(&ticker, &tick_types, window) {
+                    Ok((correlation_id, event_queue)) => {
+                        pending.push((correlation_id, event_queue, cutoff))
+                    }
+                    Err(e) => {
+                        let sent: Vec<CorrelationId> =
+                            pending.iter().map(|(id, _, _)| *id).collect();
+                        self.cancel_pending(&sent);
+                        return Err(e);
+                    }
+                }
             }
 
-            let mut correlation_id = self.new_correlation_id();
-            for event in self.send(request, &mut correlation_id)? {
-                let mut event = event?;
-                for message in event.messages() {
-                    let msg_status = &message.message_type.status;
-                    match msg_status {
-                        MessageStatus::Active => is_over = false,
-                        _ => is_over = true,
+            let ids: Vec<CorrelationId> = pending.iter().map(|(id, _, _)| *id).collect();
+            for (index, (correlation_id, event_queue, cutoff)) in pending.into_iter().enumerate() {
+                let events = SessionEvents::new(self, correlation_id, event_queue);
+                let res = events.control(control.clone()).try_for_each(|event| {
+                    for message in event?.messages() {
+                        let mut ticks = vec![];
+                        process_message_ts_tick_data(
+                            &mut message.element(),
+                            ticker.as_str(),
+                            &mut ticks,
+                        )?;
+                        ref_data.extend(
+                            ticks
+                                .into_iter()
+                                .filter(|tick| cutoff.map(|end| tick.date < end).unwrap_or(true)),
+                        );
                     }
-
-                    process_message_ts_tick_data(
-                        &mut message.element(),
-                        ticker.as_str(),
-                        &mut ref_data,
-                    )?;
-                }
-                let event_type = event.event_type();
-                if event_type == EventType::Response {
-                    is_over = true;
+                    Ok(())
+                });
+                if let Err(e) = res {
+                    self.cancel_pending(&ids[index + 1..]);
+                    return Err(e);
                 }
             }
+        }
+        Ok(())
+    }
 
-            if is_over {
-                break;
-            }
+    /// Send the intraday tick request of a window on its own event queue
+    fn send_window(
+        &mut self,
+        ticker: &str,
+        tick_types: &[TickTypes],
+        window: &HistIntradayOptions,
+    ) -> Result<(CorrelationId, EventQueue), Error> {
+        let service = BlpServices::ReferenceData;
+        let req_t = RequestTypes::IntradayTick;
+        let mut request = self.create_request(service, req_t)?;
+
+        request.element().set_named(&SECURITY, ticker)?;
+
+        window.apply(&mut request)?;
+
+        for field in tick_types.iter() {
+            let tick_type: &str = field.into();
+            request.append_named(&EVENT_TYPES, tick_type)?;
+        }
+
+        let mut correlation_id = self.new_correlation_id();
+        let event_queue = self.send_queued(request, &mut correlation_id)?;
+        Ok((correlation_id, event_queue))
+    }
+
+    /// Cancel requests which are not read anymore, e.g. the rest of a batch after an error
+    fn cancel_pending(&mut self, correlation_ids: &[CorrelationId]) {
+        if self.replay.is_some() || correlation_ids.is_empty() {
+            return;
+        }
+        if let Err(e) = self.cancel(correlation_ids, None) {
+            log::warn!("Cancelling request failed: {}", e);
         }
-        Ok(ref_data)
     }
 
     /// Get reference data for `HistoricalData` items
@@ -735,10 +1593,21 @@ impl Session {
     where
         R: RefData,
     {
+        self.field_info_for(R::FIELDS, top_fields, sub_fields)
+    }
+
+    /// Get the field information of field mnemonics or ids
+    #[inline(always)]
+    pub fn field_info_for(
+        &mut self,
+        field_ids: &[&str],
+        top_fields: Option<Vec<&str>>,
+        sub_fields: Option<Vec<&str>>,
+    ) -> Result<Vec<FieldSeries>, Error> {
         let mut ref_data: Vec<FieldSeries> = vec![];
 
         // split request as necessary to comply with bloomberg size limitations
-        for fields in R::FIELDS.chunks(MAX_HISTDATA_FIELDS) {
+        for fields in field_ids.chunks(MAX_HISTDATA_FIELDS) {
             loop {
                 // add next batch of securities and exit loop if empty
                 let service = BlpServices::ApiFields;
@@ -873,6 +1742,11 @@ impl Session {
         Ok(ref_data)
     }
 
+    /// Get all fields of the field type, reading every partial response
+    pub fn field_list_all(&mut self, field: FieldTypes) -> Result<Vec<FieldSeries>, Error> {
+        self.field_list(u64::MAX, field)
+    }
+
     #[inline(always)]
     pub fn lookup_security<S: Into<String>>(
         &mut self,
@@ -881,10 +1755,39 @@ impl Session {
         yk: Option<YellowKey>,
         lo: Option<Language>,
     ) -> Result<SecurityLookUp, Error> {
+        let control = RequestControl::default();
+        self.lookup_security_with(name, max_results, yk, lo, &control)
+            .map_err(Error::from)
+    }
+
+    /// Look up securities, stopping at the deadline or cancellation of the control
+    ///
+    /// # Note
+    /// A stopped request is cancelled, the results received so far are returned with `Error::TimeOut`.
+    pub fn lookup_security_with<S: Into<String>>(
+        &mut self,
+        name: S,
+        max_results: i32,
+        yk: Option<YellowKey>,
+        lo: Option<Language>,
+        control: &RequestControl,
+    ) -> PartialResult<SecurityLookUp> {
         let name = name.into();
-        let q_name = name.clone();
         let mut ref_data: SecurityLookUpBuilder = SecurityLookUpBuilder::default();
-        ref_data.query(q_name);
+        ref_data.query(name.clone());
+        let res = self.lookup_security_into(name, max_results, yk, lo, control, &mut ref_data);
+        partial_result(res, ref_data.build())
+    }
+
+    fn lookup_security_into(
+        &mut self,
+        name: String,
+        max_results: i32,
+        yk: Option<YellowKey>,
+        lo: Option<Language>,
+        control: &RequestControl,
+        ref_data: &mut SecurityLookUpBuilder,
+    ) -> Result<(), Error> {
         let yk: &str = yk.unwrap_or_default().into();
         let lg: &str = lo.unwrap_or_default().into();
 
@@ -904,9 +1807,10 @@ impl Session {
             element.set_named(&MAX_RESULTS, max_results)?;
 
             let mut correlation_id = self.new_correlation_id();
-            for event in self.send(request, &mut correlation_id)? {
+            let events = self.send(request, &mut correlation_id)?;
+            for event in events.control(control.clone()) {
                 for message in event?.messages() {
-                    process_message_sec_look_up(&mut message.element(), &mut ref_data)?;
+                    process_message_sec_look_up(&mut message.element(), ref_data)?;
                 }
                 is_empty = true
             }
@@ -914,8 +1818,7 @@ impl Session {
                 break;
             }
         }
-        let ref_data = ref_data.build();
-        Ok(ref_data)
+        Ok(())
     }
 
     #[inline(always)]
@@ -1030,7 +1933,123 @@ impl Session {
 impl Drop for Session {
     fn drop(&mut self) {
         unsafe { blpapi_Session_destroy(self.ptr) }
+        // The handler may run until the session is destroyed
+        drop(self.callback.take());
+    }
+}
+
+/// Session an event was delivered on, passed to the `on_event` closure
+///
+/// # Note
+/// Only valid during the call, the session is owned by `Session`.
+#[derive(Debug)]
+pub struct SessionRef {
+    ptr: *mut blpapi_Session_t,
+}
+
+impl SessionRef {
+    /// Open the service asynchronously, a `ServiceStatus` event follows
+    pub fn open_service(
+        &self,
+        service: &BlpServices,
+        correlation_id: &mut CorrelationId,
+    ) -> Result<(), Error> {
+        let service_str: &str = service.into();
+        let c_service = CString::new(service_str).unwrap_or_default();
+        let res = unsafe {
+            blpapi_Session_openServiceAsync(self.ptr, c_service.as_ptr(), &mut correlation_id.id)
+        };
+        Error::check(res)
+    }
+
+    /// Get opened service
+    pub fn get_service(&self, service: &BlpServices) -> Result<Service, Error> {
+        let blp_serv: &str = service.into();
+        let name = CString::new(blp_serv).unwrap();
+        let mut service_ptr = ptr::null_mut();
+        let res = unsafe {
+            blpapi_Session_getService(self.ptr, &mut service_ptr as *mut _, name.as_ptr())
+        };
+        Error::check(res)?;
+        Ok(Service {
+            ptr: service_ptr,
+            service: service.clone(),
+            status: BlpServiceStatus::Active,
+        })
     }
+
+    /// Send the request, the responses are delivered to the closure
+    pub fn send_request(
+        &self,
+        request: &Request,
+        correlation_id: &mut CorrelationId,
+    ) -> Result<(), Error> {
+        let res = unsafe {
+            blpapi_Session_sendRequest(
+                self.ptr,
+                request.ptr,
+                &mut correlation_id.id,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null(),
+                0,
+            )
+        };
+        Error::check(res)
+    }
+
+    /// Cancel the requests or subscriptions of the correlation ids
+    pub fn cancel(&self, correlation_ids: &[CorrelationId]) -> Result<(), Error> {
+        let ptr = self.ptr as *mut blpapi_AbstractSession_t;
+        Error::check(cancel_correlation_ids(ptr, correlation_ids, None))
+    }
+}
+
+/// Handler passed to the C session for `on_event`, panics must not unwind into C
+unsafe extern "C" fn event_callback(
+    event: *mut blpapi_Event_t,
+    session: *mut blpapi_Session_t,
+    user_data: *mut c_void,
+) {
+    if event.is_null() || user_data.is_null() {
+        return;
+    }
+    let callback = &*(user_data as *const EventCallback);
+    // The event is owned by the SDK, `Event` releases its own reference
+    blpapi_Event_addRef(event);
+    let event = EventBuilder::default().ptr(event).build();
+    let session = SessionRef { ptr: session };
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        if let Some(recorder) = &callback.recorder {
+            if let Err(e) = recorder.record(&event) {
+                log::warn!("Recording event failed: {}", e);
+            }
+        }
+        callback.connection.process_event(&event);
+        (callback.handler)(event, &session)
+    }));
+    if res.is_err() {
+        log::error!("Panic in session event handler");
+    }
+}
+
+/// Result of a request, keeping the data received before an error
+fn partial_result<T>(res: Result<(), Error>, data: T) -> PartialResult<T> {
+    match res {
+        Ok(()) => Ok(data),
+        Err(error) => Err(Box::new(Partial { data, error })),
+    }
+}
+
+/// Next replayed event, an error once the recording is exhausted
+fn next_replay_event(replay: &Mutex<EventReplay>) -> Result<Event, Error> {
+    replay.lock().unwrap().next_event().unwrap_or_else(|| {
+        Err(Error::struct_error(
+            "Session",
+            "next_event",
+            "All recorded events replayed",
+        ))
+    })
 }
 
 #[inline(always)]
@@ -1085,7 +2104,7 @@ fn process_message<R: RefData>(
 }
 
 #[inline(always)]
-fn process_message_ts<R: RefData>(
+pub(crate) fn process_message_ts<R: RefData>(
     message: &mut Element,
     ts_vec: &mut Vec<TimeSeries<R, DateType>>,
 ) -> Result<(), Error> {
@@ -1138,7 +2157,7 @@ fn process_message_ts<R: RefData>(
 }
 
 #[inline(always)]
-fn process_message_ts_tick_data(
+pub(crate) fn process_message_ts_tick_data(
     message: &mut Element,
     ticker: &str,
     ts_vec: &mut Vec<TimeSeries<TickData, IntradayDateType>>,
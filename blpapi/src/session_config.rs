@@ -0,0 +1,615 @@
+//! Session options from a TOML file (feature `config`) or from environment variables.
+//! Other file formats, e.g. YAML, are not supported.
+
+use crate::{
+    auth_options::{
+        AuthApplicationBuilder, AuthOptionsBuilder, AuthTokenBuilder, AuthUserBuilder,
+        ManualOptions,
+    },
+    correlation_id::CorrelationIdBuilder,
+    session_options::{Authentication, ClientMode, SessionOptionsBuilder},
+    socks_5_config::Socks5ConfigBuilder,
+    tls_options::{TlsOptions, TlsOptionsFile},
+    Error,
+};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+/// Keys of the session configuration
+///
+/// ```toml
+/// servers = ["localhost:8194", "backup:8194"]
+/// session_name = "my-app"
+/// client_mode = "sapi"                    # auto, dapi, sapi, compat33x
+/// authentication = "application_only"     # os_logon, directory_service, application_only, appname_and_key
+/// application_identity_key = "..."
+/// connect_timeout = 5000
+/// auto_restart_on_disconnect = true
+/// max_pending_requests = 1024
+/// num_start_attempts = 3
+/// max_event_queue_size = 10000
+/// slow_consumer_warning_low_water_mark = 0.5
+/// slow_consumer_warning_high_water_mark = 0.75
+/// keep_alive = true
+/// keep_alive_inactivity_time = 20000
+/// keep_alive_response_timeout = 5000
+/// service_check_timeout = 120000
+/// service_download_timeout = 120000
+/// default_subscription_service = "//blp/mktdata"
+/// default_topic_prefix = "/ticker/"
+/// record_subscription_data_receive_times = false
+/// bandwidth_save_mode_disabled = false
+///
+/// [auth]
+/// user = "logon"                          # logon, directory, manual
+/// directory_property = "mail"
+/// user_id = "..."
+/// ip_address = "..."
+/// application = "my-app"
+/// token = "..."
+///
+/// [tls]
+/// client_credentials = "client.pk12"
+/// client_credentials_password = "..."
+/// trusted_certificates = "root.pk7"
+/// handshake_timeout = 10000
+/// crl_fetch_timeout = 20000
+///
+/// [socks5]
+/// host = "proxy"
+/// port = 1080
+/// ```
+///
+/// Environment variables are the prefixed upper case keys, e.g. `BLP_SERVERS`
+/// or `BLP_TLS_CLIENT_CREDENTIALS` for the prefix `BLP`, lists are comma separated.
+pub const CONFIG_KEYS: &[&str] = &[
+    "servers",
+    "session_name",
+    "client_mode",
+    "authentication",
+    "application_identity_key",
+    "connect_timeout",
+    "auto_restart_on_disconnect",
+    "max_pending_requests",
+    "num_start_attempts",
+    "max_event_queue_size",
+    "slow_consumer_warning_low_water_mark",
+    "slow_consumer_warning_high_water_mark",
+    "keep_alive",
+    "keep_alive_inactivity_time",
+    "keep_alive_response_timeout",
+    "service_check_timeout",
+    "service_download_timeout",
+    "default_subscription_service",
+    "default_topic_prefix",
+    "record_subscription_data_receive_times",
+    "bandwidth_save_mode_disabled",
+    "auth.user",
+    "auth.directory_property",
+    "auth.user_id",
+    "auth.ip_address",
+    "auth.application",
+    "auth.token",
+    "tls.client_credentials",
+    "tls.client_credentials_password",
+    "tls.trusted_certificates",
+    "tls.handshake_timeout",
+    "tls.crl_fetch_timeout",
+    "socks5.host",
+    "socks5.port",
+];
+
+/// User of the `AuthOptions`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthUserConfig {
+    LogonName,
+    ActiveDirectory(String),
+    Manual(ManualOptions),
+}
+
+/// `AuthOptions` of the configuration, user, application or token
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuthConfig {
+    pub user: Option<AuthUserConfig>,
+    pub application: Option<String>,
+    pub token: Option<String>,
+}
+
+/// `TlsOptions` created from files
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsConfig {
+    pub client_credentials: PathBuf,
+    pub client_credentials_password: String,
+    pub trusted_certificates: PathBuf,
+    pub handshake_timeout: Option<isize>,
+    pub crl_fetch_timeout: Option<isize>,
+}
+
+/// Proxy of all server addresses
+#[derive(Debug, Clone, PartialEq)]
+pub struct Socks5Settings {
+    pub host: String,
+    pub port: u16,
+}
+
+/// Validated session configuration, see `CONFIG_KEYS` for the schema
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionConfig {
+    pub servers: Vec<(String, u16)>,
+    pub session_name: Option<String>,
+    pub client_mode: Option<ClientMode>,
+    pub authentication: Option<Authentication>,
+    pub application_identity_key: Option<String>,
+    pub connect_timeout: Option<u32>,
+    pub auto_restart_on_disconnect: Option<bool>,
+    pub max_pending_requests: Option<u16>,
+    pub num_start_attempts: Option<u16>,
+    pub max_event_queue_size: Option<usize>,
+    pub slow_consumer_warning_marks: Option<(f32, f32)>,
+    pub keep_alive: Option<bool>,
+    pub keep_alive_inactivity_time: Option<isize>,
+    pub keep_alive_response_timeout: Option<isize>,
+    pub service_check_timeout: Option<isize>,
+    pub service_download_timeout: Option<isize>,
+    pub default_subscription_service: Option<String>,
+    pub default_topic_prefix: Option<String>,
+    pub record_subscription_data_receive_times: Option<bool>,
+    pub bandwidth_save_mode_disabled: Option<bool>,
+    pub auth: Option<AuthConfig>,
+    pub tls: Option<TlsConfig>,
+    pub socks5: Option<Socks5Settings>,
+}
+
+impl SessionConfig {
+    /// Parse the configuration from TOML
+    #[cfg(feature = "config")]
+    pub fn from_toml(input: &str) -> Result<Self, Error> {
+        let table: toml::Table = input.parse().map_err(|e: toml::de::Error| Error::Config {
+            key: String::new(),
+            msg: e.message().to_string(),
+        })?;
+        let mut values = BTreeMap::new();
+        flatten_toml("", &table, &mut values)?;
+        Self::from_values(values)
+    }
+
+    /// Read the configuration from a TOML file
+    #[cfg(feature = "config")]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        Self::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// Read the configuration from the environment variables of the prefix
+    pub fn from_env(prefix: &str) -> Result<Self, Error> {
+        Self::from_vars(prefix, std::env::vars())
+    }
+
+    /// Read the configuration from variables, e.g. `("BLP_SERVERS", "localhost:8194")`
+    pub fn from_vars<I, K, V>(prefix: &str, vars: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let vars: BTreeMap<String, String> = vars
+            .into_iter()
+            .map(|(k, v)| (k.as_ref().to_string(), v.into()))
+            .collect();
+        let values = CONFIG_KEYS
+            .iter()
+            .filter_map(|key| {
+                let var = format!("{}_{}", prefix, key.replace('.', "_")).to_uppercase();
+                vars.get(&var).map(|value| (key.to_string(), value.clone()))
+            })
+            .collect();
+        Self::from_values(values)
+    }
+
+    /// Validate the values of the keys, unknown keys are an error
+    pub fn from_values(mut values: BTreeMap<String, String>) -> Result<Self, Error> {
+        if let Some(key) = values.keys().find(|k| !CONFIG_KEYS.contains(&k.as_str())) {
+            return Err(config_error(key, "Unknown key"));
+        }
+        let v = &mut values;
+
+        let servers = match v.remove("servers") {
+            Some(servers) => servers
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| parse_server("servers", s))
+                .collect::<Result<Vec<_>, Error>>()?,
+            None => vec![],
+        };
+
+        let low: Option<f32> = take(v, "slow_consumer_warning_low_water_mark")?;
+        let high: Option<f32> = take(v, "slow_consumer_warning_high_water_mark")?;
+        let slow_consumer_warning_marks = match (low, high) {
+            (None, None) => None,
+            (Some(low), Some(high)) if (0.0..=1.0).contains(&low) && low < high && high <= 1.0 => {
+                Some((low, high))
+            }
+            (Some(_), Some(_)) => {
+                return Err(config_error(
+                    "slow_consumer_warning_high_water_mark",
+                    "Expected 0 <= low < high <= 1",
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(config_error(
+                    "slow_consumer_warning_low_water_mark",
+                    "Missing, both marks are required",
+                ))
+            }
+            (Some(_), None) => {
+                return Err(config_error(
+                    "slow_consumer_warning_high_water_mark",
+                    "Missing, both marks are required",
+                ))
+            }
+        };
+
+        let client_mode = match v.remove("client_mode") {
+            Some(mode) => Some(parse_client_mode(&mode)?),
+            None => None,
+        };
+        let authentication = match v.remove("authentication") {
+            Some(auth) => Some(parse_authentication(&auth)?),
+            None => None,
+        };
+
+        Ok(Self {
+            servers,
+            session_name: v.remove("session_name"),
+            client_mode,
+            authentication,
+            application_identity_key: v.remove("application_identity_key"),
+            connect_timeout: take(v, "connect_timeout")?,
+            auto_restart_on_disconnect: take(v, "auto_restart_on_disconnect")?,
+            max_pending_requests: take(v, "max_pending_requests")?,
+            num_start_attempts: take(v, "num_start_attempts")?,
+            max_event_queue_size: take(v, "max_event_queue_size")?,
+            slow_consumer_warning_marks,
+            keep_alive: take(v, "keep_alive")?,
+            keep_alive_inactivity_time: take(v, "keep_alive_inactivity_time")?,
+            keep_alive_response_timeout: take(v, "keep_alive_response_timeout")?,
+            service_check_timeout: take(v, "service_check_timeout")?,
+            service_download_timeout: take(v, "service_download_timeout")?,
+            default_subscription_service: v.remove("default_subscription_service"),
+            default_topic_prefix: v.remove("default_topic_prefix"),
+            record_subscription_data_receive_times: take(
+                v,
+                "record_subscription_data_receive_times",
+            )?,
+            bandwidth_save_mode_disabled: take(v, "bandwidth_save_mode_disabled")?,
+            auth: parse_auth(v)?,
+            tls: parse_tls(v)?,
+            socks5: parse_socks5(v)?,
+        })
+    }
+
+    /// Apply the configuration on the builder, unset keys keep the values of the builder
+    pub fn apply(self, mut builder: SessionOptionsBuilder) -> Result<SessionOptionsBuilder, Error> {
+        if !self.servers.is_empty() {
+            builder.server_host = None;
+            builder.server_port = None;
+            builder.server_index = None;
+            builder.server_addresses = None;
+            for (index, (host, port)) in self.servers.into_iter().enumerate() {
+                builder = builder.set_server_address(host, port, index);
+            }
+        }
+        if let Some(name) = self.session_name {
+            builder = builder.set_session_name(name);
+        }
+        if let Some(mode) = self.client_mode {
+            builder = builder.set_client_mode(mode);
+        }
+        if let Some(auth) = self.authentication {
+            builder = builder.set_authentication_options(auth);
+        }
+        if let Some(key) = self.application_identity_key {
+            builder = builder.set_application_identity_key(key);
+        }
+        if let Some(ms) = self.connect_timeout {
+            builder = builder.set_connect_timeout(ms);
+        }
+        if let Some(restart) = self.auto_restart_on_disconnect {
+            builder = builder.set_auto_restart_on_disconnect(restart);
+        }
+        if let Some(no) = self.max_pending_requests {
+            builder = builder.set_max_pending_requests(no);
+        }
+        if let Some(no) = self.num_start_attempts {
+            builder = builder.set_num_start_attempts(no);
+        }
+        if let Some(no) = self.max_event_queue_size {
+            builder = builder.set_max_event_queue_size(no);
+        }
+        if let Some((low, high)) = self.slow_consumer_warning_marks {
+            builder = builder.set_both_slow_consumer_warning_marks(low, high);
+        }
+        if let Some(enable) = self.keep_alive {
+            builder = builder.set_keep_alive(enable);
+        }
+        if let Some(ms) = self.keep_alive_inactivity_time {
+            builder = builder.set_default_keep_alive_inactivity_time(ms);
+        }
+        if let Some(ms) = self.keep_alive_response_timeout {
+            builder = builder.set_default_keep_alive_response_timeout(ms);
+        }
+        if let Some(ms) = self.service_check_timeout {
+            builder = builder.set_service_check_timeout(ms);
+        }
+        if let Some(ms) = self.service_download_timeout {
+            builder = builder.set_service_download_timeout(ms);
+        }
+        if let Some(service) = self.default_subscription_service {
+            builder = builder.set_default_subscription_service(service);
+        }
+        if let Some(prefix) = self.default_topic_prefix {
+            builder = builder.set_default_topic_prefix(prefix);
+        }
+        if let Some(record) = self.record_subscription_data_receive_times {
+            builder = builder.set_record_subscription_data_receive_times(record);
+        }
+        if let Some(disabled) = self.bandwidth_save_mode_disabled {
+            builder = builder.set_bandwidth_save_mode_disabled(disabled);
+        }
+        if let Some(auth) = self.auth {
+            builder = apply_auth(builder, auth);
+        }
+        if let Some(tls) = self.tls {
+            let mut options = TlsOptions::create_from_files(TlsOptionsFile::new(
+                tls.client_credentials.to_string_lossy().into_owned(),
+                tls.client_credentials_password,
+                tls.trusted_certificates.to_string_lossy().into_owned(),
+            ));
+            if let Some(ms) = tls.handshake_timeout {
+                options.set_tls_handshake_timeout_ms(ms);
+            }
+            if let Some(ms) = tls.crl_fetch_timeout {
+                options.set_crl_fetch_timeout_ms(ms);
+            }
+            builder = builder.set_tls_options(options);
+        }
+        if let Some(socks5) = self.socks5 {
+            let config = Socks5ConfigBuilder::default()
+                .set_host_name(socks5.host)
+                .map_err(|_| config_error("socks5.host", "Invalid host name"))?
+                .set_port(socks5.port)
+                .build();
+            builder = builder.set_server_address_socks5config(config);
+        }
+        Ok(builder)
+    }
+}
+
+impl SessionOptionsBuilder {
+    /// Default options with the configuration of the TOML file, see `CONFIG_KEYS`
+    #[cfg(feature = "config")]
+    pub fn from_config<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        SessionConfig::from_file(path)?.apply(Self::default())
+    }
+
+    /// Default options with the configuration of the environment variables of the prefix
+    pub fn from_env(prefix: &str) -> Result<Self, Error> {
+        SessionConfig::from_env(prefix)?.apply(Self::default())
+    }
+}
+
+fn apply_auth(builder: SessionOptionsBuilder, auth: AuthConfig) -> SessionOptionsBuilder {
+    let mut options = AuthOptionsBuilder::default();
+    if let Some(user) = auth.user {
+        let user = match user {
+            AuthUserConfig::LogonName => {
+                AuthUserBuilder::new().set_logon_name(Authentication::OsLogon)
+            }
+            AuthUserConfig::ActiveDirectory(property) => {
+                AuthUserBuilder::new().set_active_directory(property)
+            }
+            AuthUserConfig::Manual(manual) => AuthUserBuilder::new().set_manual_options(manual),
+        };
+        options = options.set_auth_user(user.build());
+    }
+    if let Some(app) = auth.application {
+        let app = AuthApplicationBuilder::default().set_auth_app(app).build();
+        options = options.set_auth_application(app);
+    }
+    if let Some(token) = auth.token {
+        let token = AuthTokenBuilder::default().set_auth_token(token).build();
+        options = options.set_auth_token(token);
+    }
+    let builder = builder.set_auth_options(options.build());
+    match builder.correlation_id {
+        Some(_) => builder,
+        None => builder.set_correlation_id(CorrelationIdBuilder::default().build()),
+    }
+}
+
+fn config_error(key: &str, msg: &str) -> Error {
+    Error::Config {
+        key: key.to_string(),
+        msg: msg.to_string(),
+    }
+}
+
+/// Remove and parse the value of the key
+fn take<T: FromStr>(values: &mut BTreeMap<String, String>, key: &str) -> Result<Option<T>, Error> {
+    match values.remove(key) {
+        Some(value) => value.trim().parse().map(Some).map_err(|_| {
+            config_error(
+                key,
+                &format!(
+                    "Invalid value {:?} of {}",
+                    value,
+                    std::any::type_name::<T>()
+                ),
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
+fn parse_server(key: &str, server: &str) -> Result<(String, u16), Error> {
+    let (host, port) = server
+        .rsplit_once(':')
+        .ok_or_else(|| config_error(key, &format!("Expected host:port, got {:?}", server)))?;
+    let port = port
+        .parse()
+        .map_err(|_| config_error(key, &format!("Invalid port of {:?}", server)))?;
+    match host.is_empty() {
+        true => Err(config_error(key, &format!("Missing host of {:?}", server))),
+        false => Ok((host.to_string(), port)),
+    }
+}
+
+fn parse_client_mode(mode: &str) -> Result<ClientMode, Error> {
+    match mode.to_lowercase().as_str() {
+        "auto" => Ok(ClientMode::Auto),
+        "dapi" => Ok(ClientMode::DApi),
+        "sapi" => Ok(ClientMode::SApi),
+        "compat33x" => Ok(ClientMode::Compat33X),
+        _ => Err(config_error(
+            "client_mode",
+            "Expected auto, dapi, sapi or compat33x",
+        )),
+    }
+}
+
+fn parse_authentication(auth: &str) -> Result<Authentication, Error> {
+    match auth.to_lowercase().as_str() {
+        "os_logon" => Ok(Authentication::OsLogon),
+        "directory_service" => Ok(Authentication::DirectoryService),
+        "application_only" => Ok(Authentication::ApplicationOnly),
+        "appname_and_key" => Ok(Authentication::AppnameAndKey),
+        _ => Err(config_error(
+            "authentication",
+            "Expected os_logon, directory_service, application_only or appname_and_key",
+        )),
+    }
+}
+
+fn parse_auth(v: &mut BTreeMap<String, String>) -> Result<Option<AuthConfig>, Error> {
+    let directory = v.remove("auth.directory_property");
+    let user_id = v.remove("auth.user_id");
+    let ip_address = v.remove("auth.ip_address");
+    let user = match v.remove("auth.user").as_deref() {
+        None => None,
+        Some("logon") => Some(AuthUserConfig::LogonName),
+        Some("directory") => Some(AuthUserConfig::ActiveDirectory(directory.ok_or_else(
+            || {
+                config_error(
+                    "auth.directory_property",
+                    "Required by auth.user = directory",
+                )
+            },
+        )?)),
+        Some("manual") => Some(AuthUserConfig::Manual(ManualOptions {
+            user_id: user_id
+                .ok_or_else(|| config_error("auth.user_id", "Required by auth.user = manual"))?,
+            ip_address: ip_address
+                .ok_or_else(|| config_error("auth.ip_address", "Required by auth.user = manual"))?,
+        })),
+        Some(_) => {
+            return Err(config_error(
+                "auth.user",
+                "Expected logon, directory or manual",
+            ))
+        }
+    };
+    let auth = AuthConfig {
+        user,
+        application: v.remove("auth.application"),
+        token: v.remove("auth.token"),
+    };
+    if auth.token.is_some() && (auth.user.is_some() || auth.application.is_some()) {
+        return Err(config_error(
+            "auth.token",
+            "A token can not be combined with auth.user or auth.application",
+        ));
+    }
+    match auth == AuthConfig::default() {
+        true => Ok(None),
+        false => Ok(Some(auth)),
+    }
+}
+
+fn parse_tls(v: &mut BTreeMap<String, String>) -> Result<Option<TlsConfig>, Error> {
+    let credentials = v.remove("tls.client_credentials");
+    let password = v.remove("tls.client_credentials_password");
+    let certificates = v.remove("tls.trusted_certificates");
+    let handshake_timeout = take(v, "tls.handshake_timeout")?;
+    let crl_fetch_timeout = take(v, "tls.crl_fetch_timeout")?;
+    if credentials.is_none()
+        && password.is_none()
+        && certificates.is_none()
+        && handshake_timeout.is_none()
+        && crl_fetch_timeout.is_none()
+    {
+        return Ok(None);
+    }
+    let required = |value: Option<String>, key: &str| {
+        value.ok_or_else(|| config_error(key, "Required by the tls options"))
+    };
+    Ok(Some(TlsConfig {
+        client_credentials: required(credentials, "tls.client_credentials")?.into(),
+        client_credentials_password: required(password, "tls.client_credentials_password")?,
+        trusted_certificates: required(certificates, "tls.trusted_certificates")?.into(),
+        handshake_timeout,
+        crl_fetch_timeout,
+    }))
+}
+
+fn parse_socks5(v: &mut BTreeMap<String, String>) -> Result<Option<Socks5Settings>, Error> {
+    let host = v.remove("socks5.host");
+    let port: Option<u16> = take(v, "socks5.port")?;
+    match (host, port) {
+        (None, None) => Ok(None),
+        (Some(host), port) if !host.is_empty() => Ok(Some(Socks5Settings {
+            host,
+            port: port.unwrap_or(1080),
+        })),
+        _ => Err(config_error("socks5.host", "Required by socks5.port")),
+    }
+}
+
+/// Flatten the tables into dotted keys, lists are comma separated
+#[cfg(feature = "config")]
+fn flatten_toml(
+    prefix: &str,
+    table: &toml::Table,
+    values: &mut BTreeMap<String, String>,
+) -> Result<(), Error> {
+    for (name, value) in table {
+        let key = match prefix.is_empty() {
+            true => name.clone(),
+            false => format!("{}.{}", prefix, name),
+        };
+        match value {
+            toml::Value::Table(table) => flatten_toml(&key, table, values)?,
+            toml::Value::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| toml_scalar(&key, item))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                values.insert(key, items.join(","));
+            }
+            value => {
+                let value = toml_scalar(&key, value)?;
+                values.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "config")]
+fn toml_scalar(key: &str, value: &toml::Value) -> Result<String, Error> {
+    match value {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(i) => Ok(i.to_string()),
+        toml::Value::Float(f) => Ok(f.to_string()),
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        _ => Err(config_error(key, "Expected a string, number or boolean")),
+    }
+}
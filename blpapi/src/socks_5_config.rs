@@ -29,6 +29,10 @@ pub struct Socks5Config {
     pub index: usize,
 }
 
+// The config handle is owned and not modified after creation, thus it is `Send` and `Sync`
+unsafe impl Send for Socks5Config {}
+unsafe impl Sync for Socks5Config {}
+
 impl Socks5ConfigBuilder {
     pub fn set_host_name<T: Into<String>>(mut self, host: T) -> Result<Self, Error> {
         let binding = host.into();
@@ -14,29 +14,131 @@ use blpapi_sys::{
 };
 
 use crate::{
+    core::BLPAPI_DEFAULT_TOPIC_PREFIX,
     correlation_id::{CorrelationId, CorrelationIdBuilder},
+    element::Element,
     options,
     overrides::SubscribeOption,
     service::BlpServices,
+    session::SubscriptionStatus,
     Error,
 };
 
+/// Reason of a subscription failure, termination or stream deactivation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubscriptionReason {
+    pub source: String,
+    pub error_code: i32,
+    pub category: String,
+    pub sub_category: Option<String>,
+    pub description: String,
+}
+
+impl SubscriptionReason {
+    /// Create a reason from the `reason` element of a status message
+    pub(crate) fn from_element(element: &Element) -> Self {
+        let source = element.element_value("source").unwrap_or_default();
+        let error_code = element.element_value("errorCode").unwrap_or_default();
+        let category = element.element_value("category").unwrap_or_default();
+        let sub_category = element.element_value("subcategory");
+        let description = element.element_value("description").unwrap_or_default();
+        Self {
+            source,
+            error_code,
+            category,
+            sub_category,
+            description,
+        }
+    }
+}
+
+/// Activation state of a single subscription stream
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StreamState {
+    pub id: String,
+    pub endpoint: Option<String>,
+    pub active: bool,
+}
+
+impl StreamState {
+    /// Create a stream state from an entry of the `streams` element
+    pub(crate) fn from_element(element: &Element, active: bool) -> Self {
+        let id = element.element_value("id").unwrap_or_default();
+        let endpoint = element
+            .get_element("endpoint")
+            .and_then(|e| e.element_value("address"));
+        Self {
+            id,
+            endpoint,
+            active,
+        }
+    }
+}
+
 /// Ticker Info for Subscription Registry
-#[derive(Debug)]
+/// Keeps the original service, fields and options to replay the subscription
+#[derive(Clone, Debug)]
 pub struct TickerInfo {
     pub ticker: String,
     pub requested_fields: HashSet<String>,
+    pub service: BlpServices,
+    pub topic_prefix: String,
+    pub fields: Vec<String>,
+    pub options: Option<Vec<SubscribeOption>>,
+    pub data_loss: u64,
+    pub status: SubscriptionStatus,
+    pub reason: Option<SubscriptionReason>,
+    pub streams: Vec<StreamState>,
+}
+
+impl TickerInfo {
+    /// Key of the subscription in the session
+    pub fn key(&self) -> SubscriptionKey {
+        SubscriptionKey {
+            service: self.service.clone(),
+            topic_prefix: self.topic_prefix.clone(),
+            ticker: self.ticker.clone(),
+        }
+    }
+}
+
+/// Identifies a subscription of a session
+///
+/// # Note
+/// The same ticker may be subscribed on several services or topic prefixes,
+/// use `Session::subscription_key` to build the key of a `Subscription`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionKey {
+    pub service: BlpServices,
+    /// Topic prefix as subscribed, e.g. `/ticker/`
+    pub topic_prefix: String,
+    pub ticker: String,
 }
 
 /// Subscription Registry
 pub type SubscriptionRegistry = Arc<Mutex<HashMap<u64, TickerInfo>>>;
 
 /// Subscription Struct
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Subscription<'a> {
     pub ticker: String,
     pub fields: Vec<&'a str>,
     pub options: Option<Vec<SubscribeOption>>,
+    pub service: BlpServices,
+    /// Topic prefix, the default topic prefix of the session options if not set
+    pub topic_prefix: Option<String>,
+}
+
+impl<'a> Default for Subscription<'a> {
+    fn default() -> Self {
+        Self {
+            ticker: String::new(),
+            fields: vec![],
+            options: None,
+            service: BlpServices::MarketData,
+            topic_prefix: None,
+        }
+    }
 }
 
 impl<'a> Subscription<'a> {
@@ -58,8 +160,22 @@ impl<'a> Subscription<'a> {
             ticker,
             fields,
             options: Some(options),
+            service: BlpServices::MarketData,
+            topic_prefix: None,
         }
     }
+
+    /// Set the service of the subscription
+    pub fn service(mut self, service: BlpServices) -> Self {
+        self.service = service;
+        self
+    }
+
+    /// Set the topic prefix of the subscription, e.g. `/ticker/` or `/chain/`
+    pub fn topic_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.topic_prefix = Some(prefix.into());
+        self
+    }
 }
 
 /// SubscriptionListBuilder Struct
@@ -163,6 +279,24 @@ impl<'a> SubscriptionList<'a> {
         corr_id: CorrelationId,
         sub_fields: Option<Vec<&str>>,
         sub_options: Option<Vec<SubscribeOption>>,
+    ) -> Result<(), Error> {
+        self.add_with_prefix(
+            BLPAPI_DEFAULT_TOPIC_PREFIX,
+            ticker,
+            corr_id,
+            sub_fields,
+            sub_options,
+        )
+    }
+
+    /// Add a subscription with a custom topic prefix, e.g. `/chain/` on `//blp/mktlist`
+    pub fn add_with_prefix(
+        &mut self,
+        topic_prefix: &str,
+        ticker: String,
+        corr_id: CorrelationId,
+        sub_fields: Option<Vec<&str>>,
+        sub_options: Option<Vec<SubscribeOption>>,
     ) -> Result<(), Error> {
         let fields: Vec<String> = match sub_fields {
             Some(fields) => fields
@@ -172,7 +306,9 @@ impl<'a> SubscriptionList<'a> {
             None => self.fields.clone().iter().map(|f| f.to_string()).collect(),
         };
 
-        if (fields.is_empty()) || (self.service == BlpServices::NoService) {
+        // Only market data requires fields, depth and list topics come without
+        let missing_fields = fields.is_empty() && self.service == BlpServices::MarketData;
+        if missing_fields || (self.service == BlpServices::NoService) {
             eprintln!("Fields: {:#?}", fields);
             eprintln!("Service: {:#?}", self.service);
             return Err(Error::NotFound(String::from(
@@ -188,7 +324,7 @@ impl<'a> SubscriptionList<'a> {
         self.correlation_map.insert(cor_id_, ticker.clone());
 
         let service: &str = (&self.service).into();
-        let sub_str = format!("{}/ticker/{}", service, ticker);
+        let sub_str = format!("{}{}{}", service, topic_prefix, ticker);
         let c_subscription = CString::new(sub_str).expect("CString conversion failed");
 
         let c_corr_id = corr_id.id;
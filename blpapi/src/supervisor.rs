@@ -0,0 +1,154 @@
+use crate::{
+    event::{SubscriptionMsg, SubscriptionStream},
+    session::Session,
+    RefData,
+};
+use std::{sync::mpsc, thread, time::Duration};
+
+const DEFAULT_INITIAL_DELAY_MS: u64 = 500;
+const DEFAULT_MAX_DELAY_MS: u64 = 30_000;
+const DEFAULT_MULTIPLIER: u32 = 2;
+
+/// Backoff policy used to restart a terminated session
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: u32,
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(DEFAULT_INITIAL_DELAY_MS),
+            max_delay: Duration::from_millis(DEFAULT_MAX_DELAY_MS),
+            multiplier: DEFAULT_MULTIPLIER,
+            max_attempts: None,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before the first attempt
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Upper bound of the delay between two attempts
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Factor applied to the delay after each failed attempt
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier.max(1);
+        self
+    }
+
+    /// Give up after the given number of attempts, retries forever if not set
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Delay before the given attempt, starting at 1
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let exp = attempt.saturating_sub(1);
+        let factor = self.multiplier.saturating_pow(exp);
+        self.initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Check if another attempt is allowed
+    pub fn allows(&self, attempt: u32) -> bool {
+        match self.max_attempts {
+            Some(max) => attempt <= max,
+            None => true,
+        }
+    }
+}
+
+/// Supervisor owning a subscription session
+/// Restarts the session with backoff once it is terminated, reopens the services
+/// and replays all subscriptions of the registry.
+pub struct SessionSupervisor {
+    session: Session,
+    policy: ReconnectPolicy,
+}
+
+// The session is moved into the supervisor thread and only used there
+unsafe impl Send for SessionSupervisor {}
+
+impl SessionSupervisor {
+    pub fn new(session: Session, policy: ReconnectPolicy) -> Self {
+        Self { session, policy }
+    }
+
+    /// Start consuming the subscriptions in a supervised thread
+    pub fn start<R>(self) -> mpsc::Receiver<SubscriptionMsg<R>>
+    where
+        R: RefData + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || self.run(tx));
+        rx
+    }
+
+    fn run<R>(mut self, tx: mpsc::Sender<SubscriptionMsg<R>>)
+    where
+        R: RefData + 'static,
+    {
+        loop {
+            let stream =
+                SubscriptionStream::<R>::new(self.session.ptr, self.session.registry.clone())
+                    .policy(self.session.slow_consumer_policy)
+                    .recorder(self.session.recorder.clone())
+                    .replay(self.session.replay.clone())
+                    .connection(self.session.connection.clone());
+            for msg in stream.flatten() {
+                if let SubscriptionMsg::Terminated = msg {
+                    break;
+                }
+                if tx.send(msg).is_err() {
+                    return;
+                }
+            }
+            if !self.reconnect(&tx) {
+                let _ = tx.send(SubscriptionMsg::Terminated);
+                return;
+            }
+        }
+    }
+
+    fn reconnect<R>(&mut self, tx: &mpsc::Sender<SubscriptionMsg<R>>) -> bool {
+        let mut attempt = 1;
+        while self.policy.allows(attempt) {
+            let delay = self.policy.delay(attempt);
+            if tx
+                .send(SubscriptionMsg::Reconnecting { attempt, delay })
+                .is_err()
+            {
+                return false;
+            }
+            thread::sleep(delay);
+            match self.session.restart() {
+                Ok(resubscribed) => {
+                    return tx
+                        .send(SubscriptionMsg::Reconnected {
+                            attempt,
+                            resubscribed,
+                        })
+                        .is_ok();
+                }
+                Err(e) => log::warn!("Session restart attempt {} failed: {}", attempt, e),
+            }
+            attempt += 1;
+        }
+        false
+    }
+}
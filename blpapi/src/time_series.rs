@@ -54,6 +54,21 @@ pub fn is_valid_datetime(input: &str) -> Result<Datetime, Error> {
     Err(Error::InvalidDatetime)
 }
 
+/// Parse a datetime or date accepted by `is_valid_datetime`, dates start at midnight
+fn parse_datetime(input: &str) -> Option<IntradayDateType> {
+    let datetime_formats = ["%Y%m%dT%H%M%S", "%Y-%m-%d T%H%M%S", "%Y-%m-%d %H:%M:%S"];
+    let date_formats = ["%Y%m%d", "%Y-%m-%d"];
+    datetime_formats
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())
+        .or_else(|| {
+            date_formats
+                .iter()
+                .find_map(|fmt| NaiveDate::parse_from_str(input, fmt).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
 fn convert_date<S: Into<String>>(x: S) -> Result<Datetime, Error> {
     let haystack = x.into();
     let year = &haystack[..4];
@@ -135,7 +150,7 @@ pub struct HistOptions {
 }
 
 /// Options for historical Intraday Tick Requests
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct HistIntradayOptions {
     /// Start date yyyyMMddThhmmss
     start_dt: String,
@@ -179,6 +194,10 @@ pub struct HistIntradayOptions {
     max_data_points: Option<i32>,
     /// Max Data Pöints Origin
     max_data_origin: Option<bool>,
+    /// Length of the windows the range is split into
+    chunk: Option<chrono::Duration>,
+    /// Number of windows requested at once
+    parallel: Option<usize>,
 }
 
 impl HistOptions {
@@ -348,6 +367,67 @@ impl HistIntradayOptions {
         }
     }
 
+    /// Replace the start and end datetime
+    pub fn window(mut self, start_dt: IntradayDateType, end_dt: IntradayDateType) -> Self {
+        self.start_dt = start_dt.format("%Y%m%dT%H%M%S").to_string();
+        self.end_dt = end_dt.format("%Y%m%dT%H%M%S").to_string();
+        self
+    }
+
+    /// Start and end datetime, `None` if either is invalid
+    pub fn range(&self) -> Option<(IntradayDateType, IntradayDateType)> {
+        Some((
+            parse_datetime(&self.start_dt)?,
+            parse_datetime(&self.end_dt)?,
+        ))
+    }
+
+    /// Maximum number of ticks per response, if set
+    pub fn data_points(&self) -> Option<i32> {
+        self.max_data_points
+    }
+
+    /// Split the range into windows of the given length, e.g. `Duration::days(1)`
+    pub fn chunk(mut self, window: chrono::Duration) -> Self {
+        self.chunk = Some(window);
+        self
+    }
+
+    /// Number of windows requested at once, only sessions with request queues run them in parallel
+    pub fn parallel(mut self, requests: usize) -> Self {
+        self.parallel = Some(requests);
+        self
+    }
+
+    /// Number of windows requested at once, at least 1
+    pub fn parallel_requests(&self) -> usize {
+        self.parallel.unwrap_or(1).max(1)
+    }
+
+    /// Options of each window of the range, ordered by time
+    ///
+    /// # Note
+    /// Without a chunk length or a valid range the options are a single window.
+    pub fn windows(&self) -> Vec<HistIntradayOptions> {
+        let (chunk, start, end) = match (self.chunk, self.range()) {
+            (Some(chunk), Some((start, end))) if chunk >= chrono::Duration::seconds(1) => {
+                (chunk, start, end)
+            }
+            _ => return vec![self.clone()],
+        };
+        let mut windows = vec![];
+        let mut from = start;
+        while from < end {
+            let to = (from + chunk).min(end);
+            windows.push(self.clone().window(from, to));
+            from = to;
+        }
+        if windows.is_empty() {
+            windows.push(self.clone());
+        }
+        windows
+    }
+
     /// Set conditional codes
     pub fn cond_codes(mut self, value: bool) -> Self {
         self.cond_codes = Some(value);
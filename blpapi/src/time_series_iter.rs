@@ -0,0 +1,349 @@
+use crate::{
+    abstract_session::AbstractSession,
+    event::{Event, SessionEvents},
+    names::{EVENT_TYPES, FIELDS_NAME, SECURITIES, SECURITY},
+    ref_data::RefData,
+    request::{Request, RequestTypes},
+    request_control::RequestControl,
+    service::BlpServices,
+    session::{
+        process_message_ts, process_message_ts_tick_data, Session, MAX_HISTDATA_FIELDS,
+        MAX_PENDING_REQUEST,
+    },
+    time_series::{
+        DateType, HistIntradayOptions, HistOptions, IntradayDateType, TickData, TickTypes,
+        TimeSeries,
+    },
+    Error,
+};
+use chrono::Timelike;
+use std::collections::VecDeque;
+
+/// Session of a lazily read request, idle between requests
+enum RequestState<'a> {
+    Idle(&'a mut Session),
+    Reading(SessionEvents<'a>),
+    Closed,
+}
+
+impl<'a> RequestState<'a> {
+    /// Send the request built on the idle session
+    fn send<F>(&mut self, build: F, control: &RequestControl) -> Result<(), Error>
+    where
+        F: FnOnce(&mut Session) -> Result<Request, Error>,
+    {
+        let session = match std::mem::replace(self, RequestState::Closed) {
+            RequestState::Idle(session) => session,
+            state => {
+                *self = state;
+                return Err(Error::struct_error(
+                    "RequestState",
+                    "send",
+                    "Previous request still running",
+                ));
+            }
+        };
+        let request = build(session)?;
+        let mut correlation_id = session.new_correlation_id();
+        let events = session.send(request, &mut correlation_id)?;
+        *self = RequestState::Reading(events.control(control.clone()));
+        Ok(())
+    }
+
+    /// Next event of the running request, `None` once it completed
+    fn next_event(&mut self) -> Option<Result<Event, Error>> {
+        let event = match self {
+            RequestState::Reading(events) => events.next(),
+            _ => return None,
+        };
+        match event {
+            Some(Err(e)) => {
+                *self = RequestState::Closed;
+                Some(Err(e))
+            }
+            Some(event) => Some(event),
+            None => {
+                if let RequestState::Reading(events) = std::mem::replace(self, RequestState::Closed)
+                {
+                    *self = RequestState::Idle(events.into_session());
+                }
+                None
+            }
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        matches!(self, RequestState::Idle(_))
+    }
+}
+
+/// Rows of `Session::bdh_iter`, decoded as each partial response arrives
+///
+/// # Note
+/// Large requests are split like `Session::bdh`, the next request is sent
+/// once all rows of the previous one were read.
+pub struct BdhIter<'a, R> {
+    state: RequestState<'a>,
+    requests: VecDeque<(Vec<String>, &'static [&'static str])>,
+    options: HistOptions,
+    control: RequestControl,
+    rows: VecDeque<Result<TimeSeries<R, DateType>, Error>>,
+}
+
+impl<'a, R: RefData> BdhIter<'a, R> {
+    pub(crate) fn new(
+        session: &'a mut Session,
+        tickers: Vec<String>,
+        options: HistOptions,
+    ) -> Self {
+        let mut requests = VecDeque::new();
+        for fields in R::FIELDS.chunks(MAX_HISTDATA_FIELDS) {
+            for tickers in tickers.chunks(MAX_PENDING_REQUEST / fields.len()) {
+                requests.push_back((tickers.to_vec(), fields));
+            }
+        }
+        Self {
+            state: RequestState::Idle(session),
+            requests,
+            options,
+            control: RequestControl::default(),
+            rows: VecDeque::new(),
+        }
+    }
+
+    /// Stop each request at the deadline or cancellation of the control
+    pub fn control(mut self, control: RequestControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    fn send_next(&mut self) -> Option<Result<(), Error>> {
+        let (tickers, fields) = self.requests.pop_front()?;
+        let options = &self.options;
+        let build = |session: &mut Session| {
+            let service = BlpServices::ReferenceData;
+            let mut request = session.create_request(service, RequestTypes::HistoricalData)?;
+            for ticker in tickers.iter() {
+                request.append_named(&SECURITIES, ticker.as_str())?;
+            }
+            options.apply(&mut request)?;
+            for field in fields {
+                request.append_named(&FIELDS_NAME, *field)?;
+            }
+            Ok(request)
+        };
+        Some(self.state.send(build, &self.control))
+    }
+}
+
+impl<R: RefData> Iterator for BdhIter<'_, R> {
+    type Item = Result<TimeSeries<R, DateType>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Some(row);
+            }
+            match self.state.next_event() {
+                Some(Ok(event)) => {
+                    for message in event.messages() {
+                        let mut rows = vec![];
+                        let res = process_message_ts(&mut message.element(), &mut rows);
+                        self.rows.extend(rows.into_iter().map(Ok));
+                        // Security errors only affect their ticker
+                        if let Err(e) = res {
+                            self.rows.push_back(Err(e));
+                        }
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None if self.state.is_idle() => {
+                    if let Err(e) = self.send_next()? {
+                        return Some(Err(e));
+                    }
+                }
+                None => return None,
+            }
+        }
+    }
+}
+
+/// Ticks of `Session::bdib_iter`, decoded as each partial response arrives
+///
+/// # Note
+/// The windows of `HistIntradayOptions::chunk` are read one after the other.
+/// Once a response returns as many ticks as `HistIntradayOptions::max_data_points`,
+/// the next page is requested from the second of the last tick on.
+/// Ticks of that second already returned are skipped, a second with more ticks
+/// than the maximum can not be paged and returns an error.
+pub struct BdibIter<'a> {
+    state: RequestState<'a>,
+    ticker: String,
+    tick_types: Vec<String>,
+    windows: VecDeque<HistIntradayOptions>,
+    window: Option<HistIntradayOptions>,
+    /// End of the window, ticks from then on are part of the next window
+    cutoff: Option<IntradayDateType>,
+    control: RequestControl,
+    rows: VecDeque<Result<TimeSeries<TickData, IntradayDateType>, Error>>,
+    /// Second of the last tick and the number of ticks returned within it
+    last: Option<(IntradayDateType, usize)>,
+    skip: usize,
+    /// Ticks returned by the running request
+    received: usize,
+}
+
+impl<'a> BdibIter<'a> {
+    pub(crate) fn new(
+        session: &'a mut Session,
+        ticker: String,
+        tick_types: Vec<TickTypes>,
+        options: HistIntradayOptions,
+    ) -> Self {
+        let tick_types = tick_types
+            .iter()
+            .map(|tick_type| <&str>::from(tick_type).to_string())
+            .collect();
+        Self {
+            state: RequestState::Idle(session),
+            ticker,
+            tick_types,
+            windows: options.windows().into(),
+            window: None,
+            cutoff: None,
+            control: RequestControl::default(),
+            rows: VecDeque::new(),
+            last: None,
+            skip: 0,
+            received: 0,
+        }
+    }
+
+    /// Stop each request at the deadline or cancellation of the control
+    pub fn control(mut self, control: RequestControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Options of the next page or window, `None` once all windows are complete
+    fn next_window(&mut self) -> Option<Result<HistIntradayOptions, Error>> {
+        if let Some(page) = self.next_page() {
+            return Some(page);
+        }
+        let window = self.windows.pop_front()?;
+        self.cutoff = match self.windows.is_empty() {
+            true => None,
+            false => window.range().map(|(_, end)| end),
+        };
+        self.last = None;
+        self.skip = 0;
+        self.received = 0;
+        self.window = Some(window.clone());
+        Some(Ok(window))
+    }
+
+    /// Options of the next page of the current window, `None` once it is complete
+    fn next_page(&mut self) -> Option<Result<HistIntradayOptions, Error>> {
+        let window = self.window.as_ref()?;
+        let max = window.data_points().filter(|max| *max > 0)? as usize;
+        if self.received < max {
+            return None;
+        }
+        let (last, count) = self.last?;
+        let (_, end) = window.range()?;
+        if last >= end {
+            return None;
+        }
+        if count >= max {
+            return Some(Err(Error::struct_error(
+                "BdibIter",
+                "next_page",
+                &format!(
+                    "More than {} ticks at {}, increase max_data_points",
+                    max, last
+                ),
+            )));
+        }
+        let page = window.clone().window(last, end);
+        self.skip = count;
+        self.received = 0;
+        Some(Ok(page))
+    }
+
+    fn send_next(&mut self) -> Option<Result<(), Error>> {
+        let options = match self.next_window()? {
+            Ok(options) => options,
+            Err(e) => {
+                self.state = RequestState::Closed;
+                return Some(Err(e));
+            }
+        };
+        let ticker = &self.ticker;
+        let tick_types = &self.tick_types;
+        let build = |session: &mut Session| {
+            let service = BlpServices::ReferenceData;
+            let mut request = session.create_request(service, RequestTypes::IntradayTick)?;
+            request.element().set_named(&SECURITY, ticker.as_str())?;
+            options.apply(&mut request)?;
+            for tick_type in tick_types.iter() {
+                request.append_named(&EVENT_TYPES, tick_type.as_str())?;
+            }
+            Ok(request)
+        };
+        Some(self.state.send(build, &self.control))
+    }
+
+    fn push_row(&mut self, row: TimeSeries<TickData, IntradayDateType>) {
+        self.received += 1;
+        if self.cutoff.map(|end| row.date >= end).unwrap_or(false) {
+            return;
+        }
+        if self.skip > 0 {
+            self.skip -= 1;
+            return;
+        }
+        let second = row.date.with_nanosecond(0).unwrap_or(row.date);
+        self.last = match self.last {
+            Some((last, count)) if last == second => Some((last, count + 1)),
+            _ => Some((second, 1)),
+        };
+        self.rows.push_back(Ok(row));
+    }
+}
+
+impl Iterator for BdibIter<'_> {
+    type Item = Result<TimeSeries<TickData, IntradayDateType>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(row) = self.rows.pop_front() {
+                return Some(row);
+            }
+            match self.state.next_event() {
+                Some(Ok(event)) => {
+                    for message in event.messages() {
+                        let mut rows = vec![];
+                        let res = process_message_ts_tick_data(
+                            &mut message.element(),
+                            &self.ticker,
+                            &mut rows,
+                        );
+                        for row in rows {
+                            self.push_row(row);
+                        }
+                        if let Err(e) = res {
+                            self.rows.push_back(Err(e));
+                        }
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None if self.state.is_idle() => {
+                    if let Err(e) = self.send_next()? {
+                        return Some(Err(e));
+                    }
+                }
+                None => return None,
+            }
+        }
+    }
+}
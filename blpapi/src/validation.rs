@@ -0,0 +1,374 @@
+use crate::{
+    constant::DataType, element::Element, request::Request, schema_dump::ElementSummary, Error,
+};
+use std::fmt::Display;
+
+/// A value of a request, checked against the schema before it is set
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<RequestValue>),
+    Sequence(Vec<(String, RequestValue)>),
+}
+
+impl RequestValue {
+    /// Create a sequence from name and value pairs
+    pub fn sequence<S: Into<String>>(values: Vec<(S, RequestValue)>) -> Self {
+        RequestValue::Sequence(
+            values
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Values set on the element, `None` if it is not set
+    pub fn from_element(element: &Element) -> Option<Self> {
+        if element.is_array() {
+            let values = (0..element.num_values())
+                .filter_map(|index| Self::value_at(element, index))
+                .collect();
+            return Some(RequestValue::Array(values));
+        }
+        if element.is_complex_type() {
+            let values = Self::children(element);
+            return (!values.is_empty()).then_some(RequestValue::Sequence(values));
+        }
+        if element.num_values() == 0 || element.is_null_value(0) {
+            return None;
+        }
+        Self::value_at(element, 0)
+    }
+
+    /// Set children of a sequence or choice element with their names
+    fn children(element: &Element) -> Vec<(String, RequestValue)> {
+        element
+            .elements()
+            .filter_map(|child| Some((child.string_name(), Self::from_element(&child)?)))
+            .collect()
+    }
+
+    /// Single value of the element at the index
+    fn value_at(element: &Element, index: usize) -> Option<Self> {
+        if element.is_complex_type() {
+            let sequence = element.get_at::<Element>(index)?;
+            return Some(RequestValue::Sequence(Self::children(&sequence)));
+        }
+        match element.data_type() {
+            DataType::BlpBool => element.get_at::<bool>(index).map(RequestValue::Bool),
+            DataType::BlpInt32 | DataType::BlpInt64 | DataType::BlpByte => {
+                element.get_at::<i64>(index).map(RequestValue::Int)
+            }
+            DataType::BlpFloat32 | DataType::BlpFloat64 | DataType::BlpDecimal => {
+                element.get_at::<f64>(index).map(RequestValue::Float)
+            }
+            _ => element.get_at::<String>(index).map(RequestValue::Str),
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            RequestValue::Bool(_) => "bool",
+            RequestValue::Int(_) => "int",
+            RequestValue::Float(_) => "float",
+            RequestValue::Str(_) => "string",
+            RequestValue::Array(_) => "array",
+            RequestValue::Sequence(_) => "sequence",
+        }
+    }
+}
+
+impl From<bool> for RequestValue {
+    fn from(arg: bool) -> Self {
+        RequestValue::Bool(arg)
+    }
+}
+
+impl From<i32> for RequestValue {
+    fn from(arg: i32) -> Self {
+        RequestValue::Int(arg as i64)
+    }
+}
+
+impl From<i64> for RequestValue {
+    fn from(arg: i64) -> Self {
+        RequestValue::Int(arg)
+    }
+}
+
+impl From<f64> for RequestValue {
+    fn from(arg: f64) -> Self {
+        RequestValue::Float(arg)
+    }
+}
+
+impl From<&str> for RequestValue {
+    fn from(arg: &str) -> Self {
+        RequestValue::Str(arg.to_string())
+    }
+}
+
+impl From<String> for RequestValue {
+    fn from(arg: String) -> Self {
+        RequestValue::Str(arg)
+    }
+}
+
+impl<V: Into<RequestValue>> From<Vec<V>> for RequestValue {
+    fn from(arg: Vec<V>) -> Self {
+        RequestValue::Array(arg.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Kind of a schema violation
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// The element is not part of the schema
+    UnknownElement,
+    /// The value can not be set on an element of this data type
+    WrongType { expected: DataType, found: String },
+    /// The value is not part of the enumeration
+    InvalidEnumeration { value: String, allowed: Vec<String> },
+    /// Less values than the minimum of the element
+    TooFewValues { min: usize, found: usize },
+    /// More values than the maximum of the element
+    TooManyValues { max: usize, found: usize },
+}
+
+/// A schema violation with the path of the element, e.g. `overrides[2].fieldId`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub path: String,
+    pub kind: ValidationErrorKind,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ValidationErrorKind::UnknownElement => write!(f, "{}: unknown element", self.path),
+            ValidationErrorKind::WrongType { expected, found } => {
+                write!(f, "{}: expected {:?}, found {}", self.path, expected, found)
+            }
+            ValidationErrorKind::InvalidEnumeration { value, allowed } => write!(
+                f,
+                "{}: '{}' is not one of [{}]",
+                self.path,
+                value,
+                allowed.join(", ")
+            ),
+            ValidationErrorKind::TooFewValues { min, found } => write!(
+                f,
+                "{}: at least {} values required, found {}",
+                self.path, min, found
+            ),
+            ValidationErrorKind::TooManyValues { max, found } => write!(
+                f,
+                "{}: at most {} values allowed, found {}",
+                self.path, max, found
+            ),
+        }
+    }
+}
+
+/// Client side validation of request values against the schema of the request
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaValidator {
+    pub schema: ElementSummary,
+}
+
+impl SchemaValidator {
+    pub fn new(schema: ElementSummary) -> Self {
+        Self { schema }
+    }
+
+    /// Create a validator from the schema definition of the request
+    pub fn from_request(request: &Request) -> Self {
+        Self::new(ElementSummary::from_schema(&request.element().definition()))
+    }
+
+    /// Validate all values of the request, returns `Error::Validation` with all violations
+    pub fn validate(&self, values: &[(&str, RequestValue)]) -> Result<(), Error> {
+        let errors = self.check(values);
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(Error::Validation(errors)),
+        }
+    }
+
+    /// Validate the values set on the request, returns `Error::Validation` with all violations
+    pub fn validate_request(&self, request: &Request) -> Result<(), Error> {
+        let errors = self.check_request(request);
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(Error::Validation(errors)),
+        }
+    }
+
+    /// Collect all violations of the values set on the request
+    pub fn check_request(&self, request: &Request) -> Vec<ValidationError> {
+        let values = RequestValue::children(&request.element());
+        let mut errors = vec![];
+        check_sequence(&self.schema, "", &values, &mut errors);
+        errors
+    }
+
+    /// Collect all violations of the values of the request
+    pub fn check(&self, values: &[(&str, RequestValue)]) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        let values: Vec<(String, RequestValue)> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        check_sequence(&self.schema, "", &values, &mut errors);
+        errors
+    }
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    match path.is_empty() {
+        true => name.to_string(),
+        false => format!("{}.{}", path, name),
+    }
+}
+
+fn error(errors: &mut Vec<ValidationError>, path: &str, kind: ValidationErrorKind) {
+    errors.push(ValidationError {
+        path: path.to_string(),
+        kind,
+    });
+}
+
+/// Check the children of a sequence or choice, missing required children are reported
+fn check_sequence(
+    schema: &ElementSummary,
+    path: &str,
+    values: &[(String, RequestValue)],
+    errors: &mut Vec<ValidationError>,
+) {
+    for (name, value) in values {
+        let child_path = join_path(path, name);
+        match schema.elements.iter().find(|e| e.name == *name) {
+            Some(child) => check_element(child, &child_path, value, errors),
+            None => error(errors, &child_path, ValidationErrorKind::UnknownElement),
+        }
+    }
+    if schema.data_type == DataType::BlpChoice {
+        if values.len() > 1 {
+            let kind = ValidationErrorKind::TooManyValues {
+                max: 1,
+                found: values.len(),
+            };
+            error(errors, path, kind);
+        }
+        return;
+    }
+    for child in schema.elements.iter().filter(|e| e.min_values > 0) {
+        if !values.iter().any(|(name, _)| *name == child.name) {
+            let kind = ValidationErrorKind::TooFewValues {
+                min: child.min_values,
+                found: 0,
+            };
+            error(errors, &join_path(path, &child.name), kind);
+        }
+    }
+}
+
+/// Check the cardinality of an element and each of its values
+fn check_element(
+    schema: &ElementSummary,
+    path: &str,
+    value: &RequestValue,
+    errors: &mut Vec<ValidationError>,
+) {
+    let values = match value {
+        RequestValue::Array(values) => values.iter().collect(),
+        value => vec![value],
+    };
+    if values.len() < schema.min_values {
+        let kind = ValidationErrorKind::TooFewValues {
+            min: schema.min_values,
+            found: values.len(),
+        };
+        error(errors, path, kind);
+    }
+    if values.len() > schema.max_values {
+        let kind = ValidationErrorKind::TooManyValues {
+            max: schema.max_values,
+            found: values.len(),
+        };
+        error(errors, path, kind);
+    }
+    let is_array = matches!(value, RequestValue::Array(_));
+    for (index, value) in values.into_iter().enumerate() {
+        let value_path = match is_array {
+            true => format!("{}[{}]", path, index),
+            false => path.to_string(),
+        };
+        check_value(schema, &value_path, value, errors);
+    }
+}
+
+/// Check the type and enumeration of a single value
+fn check_value(
+    schema: &ElementSummary,
+    path: &str,
+    value: &RequestValue,
+    errors: &mut Vec<ValidationError>,
+) {
+    let data_type = &schema.data_type;
+    let accepted = match value {
+        RequestValue::Bool(_) => matches!(data_type, DataType::BlpBool),
+        RequestValue::Int(_) => matches!(
+            data_type,
+            DataType::BlpInt32
+                | DataType::BlpInt64
+                | DataType::BlpFloat32
+                | DataType::BlpFloat64
+                | DataType::BlpDecimal
+                | DataType::BlpByte
+        ),
+        RequestValue::Float(_) => matches!(
+            data_type,
+            DataType::BlpFloat32 | DataType::BlpFloat64 | DataType::BlpDecimal
+        ),
+        RequestValue::Str(_) => matches!(
+            data_type,
+            DataType::BlpString
+                | DataType::BlpChar
+                | DataType::BlpEnumeration
+                | DataType::BlpDate
+                | DataType::BlpDatetime
+                | DataType::BlpTime
+        ),
+        RequestValue::Sequence(_) => {
+            matches!(data_type, DataType::BlpSequence | DataType::BlpChoice)
+        }
+        RequestValue::Array(_) => false,
+    };
+    // Unknown data types are left to the C layer
+    if !accepted && *data_type != DataType::Unknown {
+        let kind = ValidationErrorKind::WrongType {
+            expected: data_type.clone(),
+            found: value.type_name().to_string(),
+        };
+        error(errors, path, kind);
+        return;
+    }
+
+    match value {
+        RequestValue::Str(s)
+            if !schema.enumeration.is_empty() && !schema.enumeration.contains(s) =>
+        {
+            let kind = ValidationErrorKind::InvalidEnumeration {
+                value: s.clone(),
+                allowed: schema.enumeration.clone(),
+            };
+            error(errors, path, kind);
+        }
+        RequestValue::Sequence(values) => check_sequence(schema, path, values, errors),
+        _ => (),
+    }
+}
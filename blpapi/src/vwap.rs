@@ -0,0 +1,212 @@
+use crate::{
+    core::{BLPAPI_DEFAULT_SERVICE_IDENTIFIER_VWAP, BLPAPI_DEFAULT_TOPIC_PREFIX},
+    element::Element,
+    overrides::SubscribeOption,
+    ref_data::{RefData, RefDataField},
+    service::BlpServices,
+    subscription_list::Subscription,
+};
+use chrono::NaiveTime;
+
+/// Default fields of a VWAP subscription
+pub const VWAP_FIELDS: &[&str] = &["RT_VWAP", "RT_VWAP_VOLUME", "RT_VWAP_TURNOVER"];
+
+const VWAP_START_TIME: &str = "VWAP_START_TIME";
+const VWAP_END_TIME: &str = "VWAP_END_TIME";
+const VWAP_MIN_SIZE: &str = "VWAP_MIN_SIZE";
+const VWAP_MAX_SIZE: &str = "VWAP_MAX_SIZE";
+const VWAP_MIN_PX: &str = "VWAP_MIN_PX";
+const VWAP_MAX_PX: &str = "VWAP_MAX_PX";
+const VWAP_EXCLUDE_COND_CODES: &str = "VWAP_EXCLUDE_COND_CODES";
+const VWAP_INCLUDE_COND_CODES: &str = "VWAP_INCLUDE_COND_CODES";
+
+/// Real time VWAP values of `//blp/mktvwap`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vwap {
+    pub vwap: Option<f64>,
+    pub volume: Option<f64>,
+    pub turnover: Option<f64>,
+}
+
+impl RefData for Vwap {
+    const FIELDS: &'static [&'static str] = VWAP_FIELDS;
+
+    fn on_field(&mut self, field: &str, element: &Element) {
+        match field {
+            "RT_VWAP" => self.vwap.set_from_element(element),
+            "RT_VWAP_VOLUME" => self.volume.set_from_element(element),
+            "RT_VWAP_TURNOVER" => self.turnover.set_from_element(element),
+            _ => (),
+        }
+    }
+}
+
+/// VWAP subscription with a custom calculation window
+#[derive(Debug, Clone)]
+pub struct VwapSubscription<'a> {
+    pub ticker: String,
+    pub fields: Vec<&'a str>,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub exclude_cond_codes: Vec<String>,
+    pub include_cond_codes: Vec<String>,
+    pub options: Vec<(String, String)>,
+    /// Topic prefix, the default topic prefix of the session options if not set
+    pub topic_prefix: Option<String>,
+}
+
+impl<'a> VwapSubscription<'a> {
+    pub fn new<S: Into<String>>(ticker: S) -> Self {
+        Self {
+            ticker: ticker.into(),
+            fields: VWAP_FIELDS.to_vec(),
+            start_time: None,
+            end_time: None,
+            min_size: None,
+            max_size: None,
+            min_price: None,
+            max_price: None,
+            exclude_cond_codes: vec![],
+            include_cond_codes: vec![],
+            options: vec![],
+            topic_prefix: None,
+        }
+    }
+
+    /// Set the subscribed fields
+    pub fn fields(mut self, fields: Vec<&'a str>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Set the topic prefix of the subscription, e.g. `/isin/`
+    pub fn topic_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.topic_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Start of the calculation window (GMT)
+    pub fn start_time(mut self, time: NaiveTime) -> Self {
+        self.start_time = Some(time);
+        self
+    }
+
+    /// End of the calculation window (GMT)
+    pub fn end_time(mut self, time: NaiveTime) -> Self {
+        self.end_time = Some(time);
+        self
+    }
+
+    /// Ignore trades below this size
+    pub fn min_size(mut self, size: u64) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    /// Ignore trades above this size
+    pub fn max_size(mut self, size: u64) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// Ignore trades below this price
+    pub fn min_price(mut self, price: f64) -> Self {
+        self.min_price = Some(price);
+        self
+    }
+
+    /// Ignore trades above this price
+    pub fn max_price(mut self, price: f64) -> Self {
+        self.max_price = Some(price);
+        self
+    }
+
+    /// Exclude trades with the condition code
+    pub fn exclude_cond_code<S: Into<String>>(mut self, code: S) -> Self {
+        self.exclude_cond_codes.push(code.into());
+        self
+    }
+
+    /// Only include trades with the condition code
+    pub fn include_cond_code<S: Into<String>>(mut self, code: S) -> Self {
+        self.include_cond_codes.push(code.into());
+        self
+    }
+
+    /// Any other VWAP option
+    pub fn option<S: Into<String>>(mut self, name: S, value: S) -> Self {
+        self.options.push((name.into(), value.into()));
+        self
+    }
+
+    /// All VWAP options of the subscription
+    pub fn subscribe_options(&self) -> Vec<SubscribeOption> {
+        let time = |t: &NaiveTime| t.format("%H:%M").to_string();
+        let mut options = vec![];
+        if let Some(start) = &self.start_time {
+            options.push(SubscribeOption::new(VWAP_START_TIME, time(start)));
+        }
+        if let Some(end) = &self.end_time {
+            options.push(SubscribeOption::new(VWAP_END_TIME, time(end)));
+        }
+        if let Some(size) = self.min_size {
+            options.push(SubscribeOption::new(VWAP_MIN_SIZE, size.to_string()));
+        }
+        if let Some(size) = self.max_size {
+            options.push(SubscribeOption::new(VWAP_MAX_SIZE, size.to_string()));
+        }
+        if let Some(price) = self.min_price {
+            options.push(SubscribeOption::new(VWAP_MIN_PX, price.to_string()));
+        }
+        if let Some(price) = self.max_price {
+            options.push(SubscribeOption::new(VWAP_MAX_PX, price.to_string()));
+        }
+        if !self.exclude_cond_codes.is_empty() {
+            let codes = self.exclude_cond_codes.join(",");
+            options.push(SubscribeOption::new(VWAP_EXCLUDE_COND_CODES, codes));
+        }
+        if !self.include_cond_codes.is_empty() {
+            let codes = self.include_cond_codes.join(",");
+            options.push(SubscribeOption::new(VWAP_INCLUDE_COND_CODES, codes));
+        }
+        for (name, value) in &self.options {
+            options.push(SubscribeOption::new(name.as_str(), value.as_str()));
+        }
+        options
+    }
+
+    /// Create the subscription for `SubscriptionList::add`
+    pub fn build(&self) -> Subscription<'a> {
+        Subscription {
+            ticker: self.ticker.clone(),
+            fields: self.fields.clone(),
+            options: Some(self.subscribe_options()),
+            service: BlpServices::Vwap,
+            topic_prefix: self.topic_prefix.clone(),
+        }
+    }
+
+    /// Full topic string of the subscription, with the default topic prefix if not set
+    pub fn topic(&self) -> String {
+        let prefix = self
+            .topic_prefix
+            .as_deref()
+            .unwrap_or(BLPAPI_DEFAULT_TOPIC_PREFIX);
+        let mut topic = format!(
+            "{}{}{}?fields={}",
+            BLPAPI_DEFAULT_SERVICE_IDENTIFIER_VWAP,
+            prefix,
+            self.ticker,
+            self.fields.join(",")
+        );
+        for option in self.subscribe_options() {
+            topic.push('&');
+            topic.push_str(&option.value);
+        }
+        topic
+    }
+}
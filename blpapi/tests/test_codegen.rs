@@ -0,0 +1,88 @@
+use blpapi::{
+    codegen::{field_ident, fields_from_json, fields_to_json, rust_type, RefDataGenerator},
+    data_series::FieldSeries,
+};
+
+fn field(id: &str, mnemonic: &str, data_type: &str, field_type: &str) -> FieldSeries {
+    FieldSeries {
+        id: id.to_string(),
+        mnemonic: mnemonic.to_string(),
+        desc: format!("{} description", mnemonic),
+        data_type: Some(data_type.to_string()),
+        field_type: Some(field_type.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_codegen_rust_type() {
+    assert_eq!(
+        rust_type(&field("PR005", "PX_LAST", "Double", "Price")),
+        "Option<f64>"
+    );
+    assert_eq!(
+        rust_type(&field("RQ005", "VOLUME", "Int64", "Real")),
+        "Option<i64>"
+    );
+    assert_eq!(
+        rust_type(&field("DS004", "ID_ISIN", "String", "Character")),
+        "Option<String>"
+    );
+    assert_eq!(
+        rust_type(&field("DY895", "DVD_HIST", "Sequence", "BulkFormat")),
+        "blpapi::ref_data::BulkElement"
+    );
+    assert_eq!(
+        rust_type(&field("PR009", "LAST_UPDATE_DT", "Date", "Date")),
+        "Option<chrono::NaiveDate>"
+    );
+}
+
+#[test]
+fn test_codegen_field_ident() {
+    assert_eq!(field_ident("PX_LAST"), Some(String::from("px_last")));
+    assert_eq!(field_ident("3MO_CALL_IMP_VOL"), None);
+    assert_eq!(field_ident("TYPE"), None);
+    assert_eq!(field_ident("EQY_SH_OUT_REAL-TIME"), None);
+}
+
+#[test]
+fn test_codegen_derive() {
+    let mut px_last = field("PR005", "PX_LAST", "Double", "Price");
+    px_last.field_documentation = Some(String::from("Last price for the security."));
+    let fields = vec![px_last, field("DS004", "ID_ISIN", "String", "Character")];
+    let code = RefDataGenerator::new("Data").generate(&fields);
+    assert!(code.starts_with("#[derive(Debug, Default, blpapi::RefData)]\npub struct Data {\n"));
+    assert!(code
+        .contains("    /// PX_LAST description\n    ///\n    /// Last price for the security.\n"));
+    assert!(code.contains("    pub px_last: Option<f64>,\n"));
+    assert!(code.contains("    pub id_isin: Option<String>,\n"));
+    assert!(!code.contains("impl blpapi::ref_data::RefData"));
+}
+
+#[test]
+fn test_codegen_manual_impl() {
+    let fields = vec![
+        field("PR005", "PX_LAST", "Double", "Price"),
+        field("OP178", "3MO_CALL_IMP_VOL", "Double", "Real"),
+    ];
+    let code = RefDataGenerator::new("Data").generate(&fields);
+    assert!(code.starts_with("#[derive(Debug, Default)]\n"));
+    assert!(code.contains("    pub f_3mo_call_imp_vol: Option<f64>,\n"));
+    assert!(code.contains("impl blpapi::ref_data::RefData for Data {\n"));
+    assert!(code.contains("        \"3MO_CALL_IMP_VOL\",\n"));
+}
+
+#[test]
+fn test_codegen_json_roundtrip() {
+    let mut px_last = field("PR005", "PX_LAST", "Double", "Price");
+    px_last.field_documentation = Some(String::from("Last \"price\"\nof the day"));
+    px_last.overrides = vec![String::from("PR092")];
+    px_last
+        .field_property
+        .insert(String::from("overridable"), String::from("true"));
+    let fields = vec![px_last, field("DS004", "ID_ISIN", "String", "Character")];
+    let parsed = fields_from_json(&fields_to_json(&fields)).unwrap();
+    assert_eq!(parsed, fields);
+    assert!(fields_from_json("{\"mnemonic\": \"PX_LAST\"}").is_err());
+}
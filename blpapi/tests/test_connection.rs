@@ -0,0 +1,58 @@
+use blpapi::{
+    connection::{ConnectionEvent, ConnectionMonitor, ConnectionState},
+    session_options::ServerAddress,
+};
+
+fn server(host: &str, port: u16, index: usize) -> ServerAddress {
+    ServerAddress {
+        host: host.into(),
+        port,
+        index,
+        socks_5_config: None,
+        socks_5_host: None,
+        socks_5_port: None,
+    }
+}
+
+#[test]
+fn test_connection_failover() {
+    let monitor =
+        ConnectionMonitor::new(vec![server("bpipe-1", 8194, 0), server("bpipe-2", 8194, 1)]);
+    let events = monitor.events();
+    assert_eq!(monitor.state(), ConnectionState::Connecting);
+    assert!(monitor.server().is_none());
+
+    assert!(monitor.connection_up("bpipe-1:8194").is_none());
+    assert!(monitor.is_up());
+    assert_eq!(monitor.server().unwrap().index, Some(0));
+
+    monitor.connection_down();
+    assert_eq!(monitor.state(), ConnectionState::Down);
+    assert!(monitor.server().is_none());
+
+    let failover = monitor.connection_up("bpipe-2:8194");
+    assert!(matches!(
+        failover,
+        Some(ConnectionEvent::Failover { ref from, ref to }) if from.index == Some(0) && to.index == Some(1)
+    ));
+    let status = monitor.status();
+    assert!(status.is_up());
+    assert_eq!(status.failovers, 1);
+    assert!(status.since.is_some());
+
+    let received: Vec<ConnectionEvent> = events.try_iter().collect();
+    assert_eq!(received.len(), 4);
+    assert!(matches!(received[1], ConnectionEvent::Down(Some(_))));
+    assert!(matches!(received[3], ConnectionEvent::Failover { .. }));
+}
+
+#[test]
+fn test_connection_unknown_server() {
+    let monitor = ConnectionMonitor::default();
+    let clone = monitor.clone();
+    monitor.connection_up("10.0.0.1:8196");
+    let server = clone.server().unwrap();
+    assert_eq!(server.host, "10.0.0.1");
+    assert_eq!(server.port, 8196);
+    assert_eq!(server.index, None);
+}
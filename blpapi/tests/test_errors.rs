@@ -0,0 +1,53 @@
+use blpapi::{
+    errors::{BlpError, BlpErrorKind, ErrorClass},
+    Error,
+};
+use std::error::Error as _;
+
+#[test]
+fn test_blp_error_kind_and_class() {
+    let err = BlpError::new(131077);
+    assert_eq!(err.kind, BlpErrorKind::DuplicateCorrelationId);
+    assert_eq!(err.class, ErrorClass::InvalidArgument);
+
+    let err = BlpError::new(393232);
+    assert_eq!(err.kind, BlpErrorKind::ServiceNotFound);
+    assert_eq!(err.class, ErrorClass::NotFound);
+
+    let err = BlpError::new(6);
+    assert_eq!(err.kind, BlpErrorKind::InternalError);
+    assert_eq!(err.class, ErrorClass::Unknown);
+
+    let err = BlpError::new(524387);
+    assert_eq!(err.kind, BlpErrorKind::Other);
+    assert_eq!(err.class, ErrorClass::Unsupported);
+}
+
+#[test]
+fn test_blp_error_source() {
+    let err: Error = BlpError::new(65545).into();
+    assert!(err.to_string().contains("IllegalState"));
+    let source = err.source().expect("BlpError source");
+    assert!(source.to_string().contains("code 65545"));
+}
+
+#[test]
+fn test_check_crate_codes() {
+    assert!(Error::check(0).is_ok());
+    assert!(matches!(Error::check(114), Err(Error::Session)));
+}
+
+#[test]
+fn test_request_failure_display() {
+    let err = Error::RequestFailure {
+        source: String::from("bbdbl9"),
+        code: -4001,
+        category: String::from("LIMIT"),
+        sub_category: Some(String::from("DAILY_LIMIT_REACHED")),
+        message: String::from("Daily capacity reached"),
+    };
+    assert_eq!(
+        err.to_string(),
+        "Request Failure: Daily capacity reached (LIMIT)"
+    );
+}
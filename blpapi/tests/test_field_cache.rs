@@ -0,0 +1,77 @@
+use blpapi::{constant::DataType, data_series::FieldSeries, field_cache::FieldCache};
+use std::time::{Duration, SystemTime};
+
+fn field(id: &str, mnemonic: &str, data_type: &str, overrides: Vec<&str>) -> FieldSeries {
+    FieldSeries {
+        id: id.to_string(),
+        mnemonic: mnemonic.to_string(),
+        data_type: Some(data_type.to_string()),
+        field_type: Some(String::from("Real")),
+        field_category: Some(String::from("Market Activity/Last")),
+        overrides: overrides.into_iter().map(String::from).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_field_cache_lookup() {
+    let mut cache = FieldCache::default();
+    cache.insert(field("PR005", "PX_LAST", "Double", vec!["DS004"]));
+    cache.insert(field("DS004", "ID_ISIN", "String", vec![]));
+
+    assert_eq!(cache.get("px_last").unwrap().id, "PR005");
+    assert_eq!(cache.get("PR005").unwrap().mnemonic, "PX_LAST");
+    assert_eq!(cache.data_type("PX_LAST"), Some(DataType::BlpFloat64));
+    assert_eq!(cache.data_type("DS004"), Some(DataType::BlpString));
+    assert_eq!(cache.category("PX_LAST"), Some("Market Activity/Last"));
+    assert_eq!(cache.is_overridable("ID_ISIN"), Some(true));
+    assert_eq!(cache.is_overridable("PX_LAST"), None);
+    assert_eq!(cache.is_overridable("VOLUME"), None);
+    assert_eq!(cache.get("VOLUME"), None);
+}
+
+#[test]
+fn test_field_cache_overridable_property() {
+    let mut cache = FieldCache::default();
+    let mut crncy = field("DS156", "EQY_FUND_CRNCY", "String", vec![]);
+    crncy
+        .field_property
+        .insert(String::from("overridable"), String::from("true"));
+    let mut px_last = field("PR005", "PX_LAST", "Double", vec![]);
+    px_last
+        .field_property
+        .insert(String::from("overridable"), String::from("false"));
+    cache.insert(crncy);
+    cache.insert(px_last);
+
+    assert_eq!(cache.is_overridable("EQY_FUND_CRNCY"), Some(true));
+    assert_eq!(cache.is_overridable("PR005"), Some(false));
+}
+
+#[test]
+fn test_field_cache_ttl() {
+    let mut cache = FieldCache::new(Duration::from_secs(60));
+    let fetched = SystemTime::now() - Duration::from_secs(120);
+    cache.insert_at(field("PR005", "PX_LAST", "Double", vec![]), fetched);
+    cache.insert(field("DS004", "ID_ISIN", "String", vec![]));
+
+    assert_eq!(cache.get("PX_LAST"), None);
+    assert_eq!(cache.len(), 2);
+    cache.purge_expired();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get("DS004").is_some());
+}
+
+#[test]
+fn test_field_cache_persist() {
+    let path = std::env::temp_dir().join(format!("blpapi_field_cache_{}.json", std::process::id()));
+    let mut cache = FieldCache::open(&path, Duration::from_secs(3600)).unwrap();
+    assert!(cache.is_empty());
+    cache.insert(field("PR005", "PX_LAST", "Double", vec!["DS004"]));
+    cache.save().unwrap();
+
+    let cache = FieldCache::open(&path, Duration::from_secs(3600)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let px_last = cache.get("PR005").unwrap();
+    assert_eq!(px_last, &field("PR005", "PX_LAST", "Double", vec!["DS004"]));
+}
@@ -0,0 +1,25 @@
+use blpapi::logging::{FileLogging, LogSeverity};
+
+#[test]
+fn test_log_severity_level() {
+    assert_eq!(LogSeverity::Off.level(), None);
+    assert_eq!(LogSeverity::Fatal.level(), Some(log::Level::Error));
+    assert_eq!(LogSeverity::Warn.level(), Some(log::Level::Warn));
+    assert_eq!(LogSeverity::Trace.level(), Some(log::Level::Trace));
+    assert_eq!(LogSeverity::from(3), LogSeverity::Warn);
+    assert_eq!(
+        LogSeverity::from(log::LevelFilter::Debug),
+        LogSeverity::Debug
+    );
+    assert!(LogSeverity::Error < LogSeverity::Info);
+}
+
+#[test]
+fn test_file_logging() {
+    let logging = FileLogging::new("blpapi.log")
+        .severity(LogSeverity::Debug)
+        .rollover(3, 20);
+    assert_eq!(logging.severity, LogSeverity::Debug);
+    assert_eq!(logging.rollover_file_count, 3);
+    assert_eq!(logging.rollover_file_limit, 20);
+}
@@ -0,0 +1,151 @@
+use blpapi::{
+    event::{EventType, SubscriptionMsg, SubscriptionStream},
+    market_bar::{bar_subscription, BarEvent, MarketBar},
+    recording::{EventReplay, RecordedEvent, RecordedMessage},
+    service::BlpServices,
+    session::SubscriptionStatus,
+    subscription_list::{SubscriptionRegistry, TickerInfo},
+};
+use chrono::{NaiveDate, NaiveTime};
+use std::sync::{Arc, Mutex};
+
+const BAR_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.mktbar' version='1.0.0.0'>",
+    "<service name='//blp/mktbar' version='1.0.0.0'>",
+    "<event name='MarketBarStart' eventType='MarketBarType'>",
+    "<eventId>1</eventId></event>",
+    "<event name='MarketBarUpdate' eventType='MarketBarType'>",
+    "<eventId>2</eventId></event>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='MarketBarType'>",
+    "<element name='TIME' type='Time' minOccurs='0'/>",
+    "<element name='DATE_TIME' type='Datetime' minOccurs='0'/>",
+    "<element name='OPEN' type='Float64' minOccurs='0'/>",
+    "<element name='HIGH' type='Float64' minOccurs='0'/>",
+    "<element name='LOW' type='Float64' minOccurs='0'/>",
+    "<element name='CLOSE' type='Float64' minOccurs='0'/>",
+    "<element name='NUMBER_OF_TICKS' type='Int64' minOccurs='0'/>",
+    "<element name='VOLUME' type='Int64' minOccurs='0'/>",
+    "</sequenceType>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+#[test]
+fn test_bar_subscription() {
+    let start = NaiveTime::from_hms_opt(13, 30, 0);
+    let end = NaiveTime::from_hms_opt(20, 0, 0);
+    let sub = bar_subscription("IBM US Equity", 5, start, end);
+    assert_eq!(sub.fields, vec!["LAST_PRICE"]);
+    let options: Vec<String> = sub.options.unwrap().into_iter().map(|o| o.value).collect();
+    assert_eq!(
+        options,
+        vec!["bar_size=5", "start_time=13:30", "end_time=20:00"]
+    );
+}
+
+#[test]
+fn test_bar_subscription_without_times() {
+    let sub = bar_subscription("IBM US Equity", 1, None, None);
+    assert_eq!(sub.options.unwrap().len(), 1);
+}
+
+fn bar_event(message_type: &str, elements: &str) -> String {
+    let event = RecordedEvent {
+        event_type: EventType::SubscriptionData,
+        messages: vec![RecordedMessage {
+            message_type: message_type.to_string(),
+            service: Some(String::from("//blp/mktbar")),
+            correlation_ids: vec![1],
+            elements: elements.to_string(),
+            ..Default::default()
+        }],
+    };
+    event.to_line().unwrap()
+}
+
+#[test]
+fn test_market_bar_from_message_replay() {
+    let start = concat!(
+        r#"{"DATE_TIME":"2025-01-02T13:30:00.000","OPEN":100.0,"HIGH":100.0,"#,
+        r#""LOW":100.0,"CLOSE":100.0,"NUMBER_OF_TICKS":1,"VOLUME":10}"#
+    );
+    let update = concat!(
+        r#"{"DATE_TIME":"2025-01-02T13:30:00.000","OPEN":100.0,"HIGH":101.5,"#,
+        r#""LOW":99.5,"CLOSE":101.0,"NUMBER_OF_TICKS":3,"VOLUME":25}"#
+    );
+    let recording = [
+        format!(
+            "{{\"service\":\"//blp/mktbar\",\"schema\":\"{}\"}}",
+            BAR_SCHEMA
+        ),
+        bar_event("MarketBarStart", start),
+        bar_event("MarketBarUpdate", update),
+    ]
+    .join("\n");
+    let replay = EventReplay::from_reader(recording.as_bytes()).unwrap();
+
+    let sub = bar_subscription("IBM US Equity", 5, None, None);
+    let registry = SubscriptionRegistry::default();
+    registry.lock().unwrap().insert(
+        1,
+        TickerInfo {
+            ticker: sub.ticker,
+            requested_fields: Default::default(),
+            service: BlpServices::MarketBar,
+            topic_prefix: String::from("/ticker/"),
+            fields: vec![],
+            options: sub.options,
+            data_loss: 0,
+            status: SubscriptionStatus::Subscribed,
+            reason: None,
+            streams: vec![],
+        },
+    );
+    let stream =
+        SubscriptionStream::with_decoder(std::ptr::null_mut(), registry, MarketBar::decoder())
+            .replay(Some(Arc::new(Mutex::new(replay))));
+    let bars: Vec<MarketBar> = stream
+        .map(Result::unwrap)
+        .filter_map(|msg| match msg {
+            SubscriptionMsg::Data { data, .. } => Some(data.data),
+            _ => None,
+        })
+        .collect();
+
+    let date_time = NaiveDate::from_ymd_opt(2025, 1, 2)
+        .unwrap()
+        .and_hms_opt(13, 30, 0);
+    assert_eq!(
+        bars,
+        vec![
+            MarketBar {
+                event: Some(BarEvent::Start),
+                date_time,
+                open: Some(100.0),
+                high: Some(100.0),
+                low: Some(100.0),
+                close: Some(100.0),
+                volume: Some(10),
+                num_ticks: Some(1),
+            },
+            MarketBar {
+                event: Some(BarEvent::Update),
+                date_time,
+                open: Some(100.0),
+                high: Some(101.5),
+                low: Some(99.5),
+                close: Some(101.0),
+                volume: Some(25),
+                num_ticks: Some(3),
+            },
+        ]
+    );
+}
@@ -0,0 +1,228 @@
+use blpapi::{
+    event::{EventType, SubscriptionMsg, SubscriptionStream},
+    market_depth::{depth_subscription, BookCommand, BookLevel, BookSide, BookType, OrderBook},
+    recording::{EventReplay, RecordedEvent, RecordedMessage},
+    service::BlpServices,
+    session::SubscriptionStatus,
+    subscription_list::{SubscriptionRegistry, TickerInfo},
+};
+use std::sync::{Arc, Mutex};
+
+const DEPTH_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.mktdepthdata' version='1.0.0.0'>",
+    "<service name='//blp/mktdepthdata' version='1.0.0.0'>",
+    "<event name='MarketDepthUpdates' eventType='MarketDepthUpdatesType'>",
+    "<eventId>1</eventId></event>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>true</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='BidEntryType'>",
+    "<element name='BID_RT' type='Float64'/>",
+    "<element name='BID_SIZE_RT' type='Int64'/>",
+    "<element name='BID_POSITION_RT' type='Int64'/>",
+    "</sequenceType>",
+    "<sequenceType name='AskEntryType'>",
+    "<element name='ASK_RT' type='Float64'/>",
+    "<element name='ASK_SIZE_RT' type='Int64'/>",
+    "<element name='ASK_POSITION_RT' type='Int64'/>",
+    "</sequenceType>",
+    "<sequenceType name='MarketDepthUpdatesType'>",
+    "<element name='MKTDEPTH_EVENT_SUBTYPE' type='String' minOccurs='0'/>",
+    "<element name='MD_TABLE_CMD_RT' type='String' minOccurs='0'/>",
+    "<element name='MBL_WINDOW_SIZE' type='Int64' minOccurs='0'/>",
+    "<element name='BID_RT' type='Float64' minOccurs='0'/>",
+    "<element name='BID_SIZE_RT' type='Int64' minOccurs='0'/>",
+    "<element name='BID_POSITION_RT' type='Int64' minOccurs='0'/>",
+    "<element name='MBL_TABLE_BID' type='BidEntryType' minOccurs='0' maxOccurs='unbounded'/>",
+    "<element name='MBL_TABLE_ASK' type='AskEntryType' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+fn level(price: f64, size: i64) -> BookLevel {
+    BookLevel {
+        price,
+        size,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_book_command_from_str() {
+    assert_eq!(BookCommand::from("ADD"), BookCommand::Add);
+    assert_eq!(
+        BookCommand::from("REPLACE_CLEAR"),
+        BookCommand::ReplaceClear
+    );
+    assert_eq!(BookCommand::from("FOO"), BookCommand::Unknown);
+}
+
+#[test]
+fn test_order_book_add_del() {
+    let mut book = OrderBook::new("VOD LN Equity");
+    book.apply(BookSide::Bid, BookCommand::Add, 1, level(100.0, 10));
+    book.apply(BookSide::Bid, BookCommand::Add, 1, level(101.0, 5));
+    assert_eq!(book.best_bid(), Some(&level(101.0, 5)));
+    assert_eq!(book.bids.len(), 2);
+
+    book.apply(BookSide::Bid, BookCommand::Del, 1, BookLevel::default());
+    assert_eq!(book.best_bid(), Some(&level(100.0, 10)));
+}
+
+#[test]
+fn test_order_book_mod_replace_clear() {
+    let mut book = OrderBook::new("VOD LN Equity");
+    book.apply(BookSide::Ask, BookCommand::Replace, 2, level(102.0, 7));
+    assert_eq!(book.asks.len(), 2);
+    book.apply(BookSide::Ask, BookCommand::Mod, 1, level(101.5, 3));
+    assert_eq!(book.best_ask(), Some(&level(101.5, 3)));
+    book.apply(
+        BookSide::Ask,
+        BookCommand::ReplaceClear,
+        1,
+        BookLevel::default(),
+    );
+    assert_eq!(book.best_ask(), Some(&BookLevel::default()));
+    book.apply(
+        BookSide::Ask,
+        BookCommand::ClearAll,
+        1,
+        BookLevel::default(),
+    );
+    assert!(book.asks.is_empty());
+}
+
+#[test]
+fn test_order_book_del_better() {
+    let mut book = OrderBook::new("VOD LN Equity");
+    for price in [99.0, 100.0, 101.0] {
+        book.apply(BookSide::Bid, BookCommand::Add, 1, level(price, 1));
+    }
+    book.apply(
+        BookSide::Bid,
+        BookCommand::DelBetter,
+        2,
+        BookLevel::default(),
+    );
+    assert_eq!(book.bids, vec![level(99.0, 1)]);
+
+    book.apply(
+        BookSide::Bid,
+        BookCommand::DelBetter,
+        5,
+        BookLevel::default(),
+    );
+    assert!(book.bids.is_empty());
+}
+
+#[test]
+fn test_order_book_window_size() {
+    let mut book = OrderBook::new("VOD LN Equity");
+    book.window_size = Some(2);
+    for price in [100.0, 99.0, 98.0] {
+        book.apply(BookSide::Bid, BookCommand::Add, 3, level(price, 1));
+    }
+    assert_eq!(book.side(BookSide::Bid).len(), 2);
+}
+
+#[test]
+fn test_depth_subscription() {
+    let sub = depth_subscription("VOD LN Equity", BookType::MarketByOrder);
+    assert!(sub.fields.is_empty());
+    assert_eq!(sub.options.unwrap()[0].value, "type=MBO");
+}
+
+/// Market depth message of the subscription with the given correlation id
+fn depth_event(correlation_id: u64, recap_type: i32, elements: &str) -> String {
+    let event = RecordedEvent {
+        event_type: EventType::SubscriptionData,
+        messages: vec![RecordedMessage {
+            message_type: String::from("MarketDepthUpdates"),
+            service: Some(String::from("//blp/mktdepthdata")),
+            correlation_ids: vec![correlation_id],
+            recap_type,
+            elements: elements.to_string(),
+            ..Default::default()
+        }],
+    };
+    event.to_line().unwrap()
+}
+
+fn depth_info(book_type: BookType) -> TickerInfo {
+    let sub = depth_subscription("VOD LN Equity", book_type);
+    TickerInfo {
+        ticker: sub.ticker,
+        requested_fields: Default::default(),
+        service: BlpServices::MarketDepth,
+        topic_prefix: String::from("/ticker/"),
+        fields: vec![],
+        options: sub.options,
+        data_loss: 0,
+        status: SubscriptionStatus::Subscribed,
+        reason: None,
+        streams: vec![],
+    }
+}
+
+#[test]
+fn test_order_book_decoder_replay() {
+    let recap = concat!(
+        r#"{"MBL_WINDOW_SIZE":10,"#,
+        r#""MBL_TABLE_BID":[{"BID_RT":100.0,"BID_SIZE_RT":10,"BID_POSITION_RT":1},"#,
+        r#"{"BID_RT":99.0,"BID_SIZE_RT":20,"BID_POSITION_RT":2}],"#,
+        r#""MBL_TABLE_ASK":[{"ASK_RT":101.0,"ASK_SIZE_RT":5,"ASK_POSITION_RT":1}]}"#
+    );
+    let add = concat!(
+        r#"{"MKTDEPTH_EVENT_SUBTYPE":"BID","MD_TABLE_CMD_RT":"ADD","#,
+        r#""BID_RT":100.5,"BID_SIZE_RT":1,"BID_POSITION_RT":1}"#
+    );
+    let del_better =
+        r#"{"MKTDEPTH_EVENT_SUBTYPE":"BID","MD_TABLE_CMD_RT":"DELBETTER","BID_POSITION_RT":1}"#;
+    let recording = [
+        format!(
+            "{{\"service\":\"//blp/mktdepthdata\",\"schema\":\"{}\"}}",
+            DEPTH_SCHEMA
+        ),
+        depth_event(1, 1, recap),
+        depth_event(2, 0, add),
+        depth_event(1, 0, del_better),
+    ]
+    .join("\n");
+    let replay = EventReplay::from_reader(recording.as_bytes()).unwrap();
+
+    // market by level and market by order books of the same ticker
+    let registry = SubscriptionRegistry::default();
+    registry
+        .lock()
+        .unwrap()
+        .insert(1, depth_info(BookType::MarketByLevel));
+    registry
+        .lock()
+        .unwrap()
+        .insert(2, depth_info(BookType::MarketByOrder));
+    let stream =
+        SubscriptionStream::with_decoder(std::ptr::null_mut(), registry, OrderBook::decoder())
+            .replay(Some(Arc::new(Mutex::new(replay))));
+    let books: Vec<OrderBook> = stream
+        .map(Result::unwrap)
+        .filter_map(|msg| match msg {
+            SubscriptionMsg::Data { data, .. } => Some(data.data),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(books.len(), 3);
+    assert_eq!(books[0].window_size, Some(10));
+    assert_eq!(books[0].bids, vec![level(100.0, 10), level(99.0, 20)]);
+    assert_eq!(books[0].asks, vec![level(101.0, 5)]);
+    assert_eq!(books[1].bids, vec![level(100.5, 1)]);
+    assert!(books[1].asks.is_empty());
+    assert_eq!(books[2].bids, vec![level(99.0, 20)]);
+    assert_eq!(books[2].asks, vec![level(101.0, 5)]);
+}
@@ -0,0 +1,163 @@
+use blpapi::{
+    event::{EventType, SubscriptionMsg, SubscriptionStream},
+    market_list::{list_subscription, ListAction, MarketList, MarketListKind, MarketListUpdate},
+    recording::{EventReplay, RecordedEvent, RecordedMessage},
+    service::BlpServices,
+    session::SubscriptionStatus,
+    subscription_list::{SubscriptionRegistry, TickerInfo},
+};
+use std::sync::{Arc, Mutex};
+
+const LIST_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.mktlist' version='1.0.0.0'>",
+    "<service name='//blp/mktlist' version='1.0.0.0'>",
+    "<event name='MarketListUpdates' eventType='MarketListUpdatesType'>",
+    "<eventId>1</eventId></event>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>true</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='SecurityType'>",
+    "<element name='SECURITY' type='String'/>",
+    "<element name='EXCHANGE' type='String' minOccurs='0'/>",
+    "</sequenceType>",
+    "<sequenceType name='MarketListUpdatesType'>",
+    "<element name='ACTION' type='String' minOccurs='0'/>",
+    "<element name='SECURITIES' type='SecurityType' minOccurs='0' maxOccurs='unbounded'/>",
+    "<element name='EXCHANGES' type='String' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+#[test]
+fn test_market_list_kind_prefix() {
+    assert_eq!(MarketListKind::Chain.topic_prefix(), "/chain/");
+    assert_eq!(MarketListKind::Secids.topic_prefix(), "/secids/");
+}
+
+#[test]
+fn test_list_action_from_str() {
+    assert_eq!(ListAction::from("ADD"), ListAction::Add);
+    assert_eq!(ListAction::from("delete"), ListAction::Remove);
+    assert_eq!(ListAction::from("FOO"), ListAction::Unknown);
+    assert_eq!(ListAction::from(""), ListAction::Unknown);
+}
+
+#[test]
+fn test_market_list_add_remove() {
+    let mut list = MarketList::new("bsym/US/IBM");
+    let update = list.add(vec!["IBM 1 C100", "IBM 1 P100"]);
+    assert_eq!(update.added.len(), 2);
+    assert!(list.contains("IBM 1 C100"));
+
+    let update = list.add(vec!["IBM 1 C100"]);
+    assert!(update.added.is_empty());
+
+    let update = list.remove(vec!["IBM 1 P100", "IBM 1 P200"]);
+    assert_eq!(update.removed, vec!["IBM 1 P100"]);
+    assert_eq!(update.members, vec!["IBM 1 C100"]);
+}
+
+#[test]
+fn test_market_list_replace() {
+    let mut list = MarketList::new("bsym/US/IBM");
+    list.add(vec!["A", "B"]);
+    let update = list.replace(vec!["B", "C"]);
+    assert_eq!(update.added, vec!["C"]);
+    assert_eq!(update.removed, vec!["A"]);
+    assert_eq!(update.members, vec!["B", "C"]);
+    assert_eq!(list.len(), 2);
+}
+
+#[test]
+fn test_list_subscription() {
+    let sub = list_subscription("bsym/US/IBM", MarketListKind::Secids);
+    assert_eq!(sub.ticker, "bsym/US/IBM");
+    assert!(sub.fields.is_empty());
+    assert_eq!(sub.topic_prefix.as_deref(), Some("/secids/"));
+}
+
+/// Market list message of the subscription with the given correlation id
+fn list_event(correlation_id: u64, recap_type: i32, elements: &str) -> String {
+    let event = RecordedEvent {
+        event_type: EventType::SubscriptionData,
+        messages: vec![RecordedMessage {
+            message_type: String::from("MarketListUpdates"),
+            service: Some(String::from("//blp/mktlist")),
+            correlation_ids: vec![correlation_id],
+            recap_type,
+            elements: elements.to_string(),
+            ..Default::default()
+        }],
+    };
+    event.to_line().unwrap()
+}
+
+fn list_info(kind: MarketListKind) -> TickerInfo {
+    TickerInfo {
+        ticker: String::from("bsym/US/IBM"),
+        requested_fields: Default::default(),
+        service: BlpServices::MarketList,
+        topic_prefix: kind.topic_prefix().to_string(),
+        fields: vec![],
+        options: None,
+        data_loss: 0,
+        status: SubscriptionStatus::Subscribed,
+        reason: None,
+        streams: vec![],
+    }
+}
+
+#[test]
+fn test_market_list_decoder_replay() {
+    let recap = r#"{"SECURITIES":[{"SECURITY":"A","EXCHANGE":"US"},{"SECURITY":"B"}]}"#;
+    let add = r#"{"ACTION":"ADD","SECURITIES":[{"SECURITY":"C"}]}"#;
+    // other string arrays are neither members nor actions
+    let remove = r#"{"ACTION":"DELETE","SECURITIES":[{"SECURITY":"A"}],"EXCHANGES":["US"]}"#;
+    let unknown = r#"{"ACTION":"FOO","SECURITIES":[{"SECURITY":"B"}]}"#;
+    let recording = [
+        format!(
+            "{{\"service\":\"//blp/mktlist\",\"schema\":\"{}\"}}",
+            LIST_SCHEMA
+        ),
+        list_event(1, 1, recap),
+        list_event(2, 0, add),
+        list_event(1, 0, remove),
+        list_event(1, 0, unknown),
+    ]
+    .join("\n");
+    let replay = EventReplay::from_reader(recording.as_bytes()).unwrap();
+
+    // chain and secids list of the same id
+    let registry = SubscriptionRegistry::default();
+    registry
+        .lock()
+        .unwrap()
+        .insert(1, list_info(MarketListKind::Chain));
+    registry
+        .lock()
+        .unwrap()
+        .insert(2, list_info(MarketListKind::Secids));
+    let stream =
+        SubscriptionStream::with_decoder(std::ptr::null_mut(), registry, MarketList::decoder())
+            .replay(Some(Arc::new(Mutex::new(replay))));
+    let updates: Vec<MarketListUpdate> = stream
+        .map(Result::unwrap)
+        .filter_map(|msg| match msg {
+            SubscriptionMsg::Data { data, .. } => Some(data.data),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(updates.len(), 3);
+    assert_eq!(updates[0].added, vec!["A", "B"]);
+    assert_eq!(updates[1].added, vec!["C"]);
+    assert_eq!(updates[1].members, vec!["C"]);
+    assert_eq!(updates[2].removed, vec!["A"]);
+    assert_eq!(updates[2].members, vec!["B"]);
+}
@@ -0,0 +1,143 @@
+use blpapi::{
+    event::EventType,
+    recording::{EventReplay, RecordLine, RecordedEvent, RecordedMessage},
+};
+use std::io::Cursor;
+
+/// Schema of a test service, attributes in single quotes to embed it in a recording line
+const TEST_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.test' version='1.0.0.0'>",
+    "<service name='//blp/test' version='1.0.0.0'>",
+    "<event name='TestEvents' eventType='TestEventType'><eventId>1</eventId></event>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema><sequenceType name='TestEventType'>",
+    "<element name='VOLUME' type='Int64' minOccurs='0'/>",
+    "<element name='PRICE' type='Float64' minOccurs='0'/>",
+    "<element name='TRADE_TIME' type='Datetime' minOccurs='0'/>",
+    "<element name='OPEN_TIME' type='Time' minOccurs='0'/>",
+    "</sequenceType></schema>",
+    "</ServiceDefinition>",
+);
+
+fn market_data_event() -> RecordedEvent {
+    RecordedEvent {
+        event_type: EventType::SubscriptionData,
+        messages: vec![RecordedMessage {
+            message_type: String::from("MarketDataEvents"),
+            service: Some(String::from("//blp/mktdata")),
+            correlation_ids: vec![1, u64::MAX],
+            time_received: Some(1_700_000_000_123_456_789),
+            elements: String::from(
+                r#"{"LAST_PRICE":101.5,"BID":[101.25],"TRADING_DT_REALTIME":"2024-01-02"}"#,
+            ),
+            ..Default::default()
+        }],
+    }
+}
+
+#[test]
+fn test_recording_event_roundtrip() {
+    let event = market_data_event();
+    let line = event.to_line().unwrap();
+    assert!(!line.contains('\n'));
+    assert_eq!(RecordLine::parse(&line).unwrap(), RecordLine::Event(event));
+}
+
+#[test]
+fn test_recording_session_message() {
+    let event = RecordedEvent {
+        event_type: EventType::SessionStatus,
+        messages: vec![RecordedMessage {
+            message_type: String::from("SessionStarted"),
+            request_id: Some(String::from("f2a8c1e0")),
+            elements: String::from(r#"{"initialEndpoints":[{"address":"localhost:8194"}]}"#),
+            ..Default::default()
+        }],
+    };
+    let parsed = RecordLine::parse(&event.to_line().unwrap()).unwrap();
+    assert_eq!(parsed, RecordLine::Event(event));
+}
+
+#[test]
+fn test_recording_service_line() {
+    let line =
+        r#"{"service":"//blp/refdata","schema":"<ServiceDefinition name=\"blp.refdata\"/>"}"#;
+    match RecordLine::parse(line).unwrap() {
+        RecordLine::Service { name, schema } => {
+            assert_eq!(name, "//blp/refdata");
+            assert_eq!(schema, "<ServiceDefinition name=\"blp.refdata\"/>");
+        }
+        _ => panic!("Expected service line"),
+    }
+    assert!(RecordLine::parse(r#"{"messages":[]}"#).is_err());
+}
+
+#[test]
+fn test_recording_exact_int64() {
+    let mut event = market_data_event();
+    event.messages[0].elements = String::from(r#"{"VOLUME":9007199254740993,"PRICE":"NaN"}"#);
+    let line = event.to_line().unwrap();
+    assert!(line.contains("9007199254740993"));
+    assert_eq!(RecordLine::parse(&line).unwrap(), RecordLine::Event(event));
+}
+
+#[test]
+fn test_recording_replay_elements() {
+    let elements = concat!(
+        r#"{"VOLUME":9007199254740993,"PRICE":"NaN","#,
+        r#""TRADE_TIME":"2024-01-02T09:30:00.123456+01:00","OPEN_TIME":"09:30:00.250"}"#,
+    );
+    let event = RecordedEvent {
+        event_type: EventType::SubscriptionData,
+        messages: vec![RecordedMessage {
+            message_type: String::from("TestEvents"),
+            service: Some(String::from("//blp/test")),
+            correlation_ids: vec![1],
+            elements: String::from(elements),
+            ..Default::default()
+        }],
+    };
+    let recording = format!(
+        "{{\"service\":\"//blp/test\",\"schema\":\"{}\"}}\n{}\n",
+        TEST_SCHEMA,
+        event.to_line().unwrap()
+    );
+    let mut replay = EventReplay::from_reader(Cursor::new(recording)).unwrap();
+    assert_eq!(replay.len(), 1);
+
+    let replayed = replay.next_event().unwrap().unwrap();
+    let recorded = RecordedEvent::from_event(&replayed);
+    assert_eq!(recorded.event_type, EventType::SubscriptionData);
+    assert_eq!(recorded.messages[0].correlation_ids, vec![1]);
+    assert_eq!(recorded.messages[0].elements, elements);
+    assert!(replay.next_event().is_none());
+}
+
+#[test]
+fn test_recording_non_finite_floats() {
+    let mut event = market_data_event();
+    event.messages[0].elements = String::from(r#"{"PRICE":NaN,"HIGH":Infinity,"LOW":-Infinity}"#);
+    let line = event.to_line().unwrap();
+    assert!(line.contains(r#""PRICE":NaN,"HIGH":Infinity,"LOW":-Infinity"#));
+    assert_eq!(RecordLine::parse(&line).unwrap(), RecordLine::Event(event));
+}
+
+#[test]
+fn test_recording_surrogate_pairs() {
+    let mut event = market_data_event();
+    event.messages[0].elements = String::from(r#"{"NAME":"smile 😀","LONE":"\uD83D!"}"#);
+    let line = event.to_line().unwrap();
+    match RecordLine::parse(&line).unwrap() {
+        RecordLine::Event(parsed) => assert_eq!(
+            parsed.messages[0].elements,
+            "{\"NAME\":\"smile \u{1F600}\",\"LONE\":\"\u{FFFD}!\"}"
+        ),
+        _ => panic!("Expected event line"),
+    }
+}
@@ -0,0 +1,56 @@
+use blpapi::{request::RequestTypes, service::Service, Error};
+
+/// Schema of a custom service with a single operation
+const CUSTOM_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.custom' version='1.0.0.0'>",
+    "<service name='//blp/custom' version='1.0.0.0'>",
+    "<operation name='PriceRequest' serviceId='1'>",
+    "<request>PriceRequest</request>",
+    "<response>PriceResponse</response>",
+    "<responseSelection>PriceResponse</responseSelection>",
+    "</operation>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='PriceRequestType'>",
+    "<element name='ticker' type='String'/>",
+    "</sequenceType>",
+    "<sequenceType name='PriceResponseType'>",
+    "<element name='price' type='Float64' minOccurs='0'/>",
+    "</sequenceType>",
+    "<element name='PriceRequest' type='PriceRequestType'/>",
+    "<element name='PriceResponse' type='PriceResponseType'/>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+#[test]
+fn test_request_types_str() {
+    let reference: &str = RequestTypes::ReferenceData.into();
+    assert_eq!(reference, "ReferenceDataRequest");
+    let custom = RequestTypes::Custom("PriceRequest");
+    let operation: &str = custom.into();
+    assert_eq!(operation, "PriceRequest");
+    assert_eq!(custom.as_str(), "PriceRequest");
+}
+
+#[test]
+fn test_request_custom_service() -> Result<(), Error> {
+    let service = Service::deserialize(CUSTOM_SCHEMA)?;
+    let operation = RequestTypes::Custom("PriceRequest");
+    let request = service.create_request(operation)?;
+    request.element().set("ticker", "IBM US Equity")?;
+    assert_eq!(
+        request.element().element_value::<String>("ticker"),
+        Some(String::from("IBM US Equity"))
+    );
+    assert!(service
+        .create_request(RequestTypes::Custom("UnknownRequest"))
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use blpapi::{
+    element::Element,
+    event::EventType,
+    recording::{EventReplay, RecordedEvent, RecordedMessage},
+    ref_data::RefData,
+    request_control::{CancellationToken, Partial, RequestControl},
+    session::{Session, SessionBuilder},
+    time_series::HistOptions,
+    Error,
+};
+use std::time::{Duration, Instant};
+
+const HISTDATA_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.refdata' version='1.0.0.0'>",
+    "<service name='//blp/refdata' version='1.0.0.0'>",
+    "<operation name='HistoricalDataRequest' serviceId='1'>",
+    "<request>HistoricalDataRequest</request>",
+    "<response>HistoricalDataResponse</response>",
+    "<responseSelection>HistoricalDataResponse</responseSelection>",
+    "</operation>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='HistoricalDataRequestType'>",
+    "<element name='securities' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='fields' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='startDate' type='String'/>",
+    "<element name='endDate' type='String'/>",
+    "</sequenceType>",
+    "<sequenceType name='HistoricalDataRowType'>",
+    "<element name='date' type='Date'/>",
+    "<element name='PX_LAST' type='Float64' minOccurs='0'/>",
+    "</sequenceType>",
+    "<sequenceType name='HistoricalSecurityDataType'>",
+    "<element name='security' type='String'/>",
+    "<element name='fieldData' type='HistoricalDataRowType' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<sequenceType name='HistoricalDataResponseType'>",
+    "<element name='securityData' type='HistoricalSecurityDataType'/>",
+    "</sequenceType>",
+    "<element name='HistoricalDataRequest' type='HistoricalDataRequestType'/>",
+    "<element name='HistoricalDataResponse' type='HistoricalDataResponseType'/>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+thread_local! {
+    /// Token cancelled once a price was read
+    static READ_TOKEN: CancellationToken = CancellationToken::new();
+}
+
+#[derive(Debug, Default)]
+struct Price {
+    px_last: Option<f64>,
+}
+
+impl RefData for Price {
+    const FIELDS: &'static [&'static str] = &["PX_LAST"];
+
+    fn on_field(&mut self, field: &str, element: &Element) {
+        if field == "PX_LAST" {
+            self.px_last = element.get_at(0);
+            READ_TOKEN.with(|token| token.cancel());
+        }
+    }
+}
+
+/// Session replaying a partial response and the final response of a `bdh`
+fn bdh_session() -> Result<Session, Error> {
+    let security = |event_type, ticker: &str, px_last: f64| RecordedEvent {
+        event_type,
+        messages: vec![RecordedMessage {
+            message_type: String::from("HistoricalDataResponse"),
+            service: Some(String::from("//blp/refdata")),
+            correlation_ids: vec![1],
+            elements: format!(
+                r#"{{"securityData":{{"security":"{}","fieldData":[{{"date":"2025-01-02","PX_LAST":{}}}]}}}}"#,
+                ticker, px_last
+            ),
+            ..Default::default()
+        }],
+    };
+    let recording = format!(
+        "{{\"service\":\"//blp/refdata\",\"schema\":\"{}\"}}\n{}\n{}\n",
+        HISTDATA_SCHEMA,
+        security(EventType::PartialResponse, "IBM US Equity", 100.0).to_line()?,
+        security(EventType::Response, "AAPL US Equity", 200.0).to_line()?
+    );
+    let replay = EventReplay::from_reader(recording.as_bytes())?;
+    Ok(SessionBuilder::default().replay(replay).build())
+}
+
+#[test]
+fn test_request_control_deadline() {
+    let control = RequestControl::default();
+    assert!(control.is_unbounded());
+    assert!(!control.is_stopped());
+
+    let control = RequestControl::default().timeout(Duration::from_secs(60));
+    assert!(!control.is_unbounded());
+    assert!(!control.is_expired());
+
+    let control = RequestControl::default().deadline(Instant::now());
+    assert!(control.is_expired());
+    assert!(control.is_stopped());
+}
+
+#[test]
+fn test_request_control_token() {
+    let token = CancellationToken::new();
+    let control = RequestControl::default().token(token.clone());
+    assert!(!control.is_cancelled());
+    token.cancel();
+    assert!(control.is_cancelled());
+    assert!(control.is_stopped());
+}
+
+#[test]
+fn test_request_control_partial() {
+    let partial = Box::new(Partial {
+        data: vec![1, 2],
+        error: Error::TimeOut,
+    });
+    assert_eq!(partial.data.len(), 2);
+    let error: Error = partial.into();
+    assert!(matches!(error, Error::TimeOut));
+}
+
+#[test]
+fn test_request_control_cancel_keeps_rows() -> Result<(), Error> {
+    let mut session = bdh_session()?;
+    let control = RequestControl::default().token(READ_TOKEN.with(Clone::clone));
+    let options = HistOptions::new("20250102", "20250102");
+    let tickers = vec!["IBM US Equity", "AAPL US Equity"];
+    match session.bdh_with::<Price>(tickers, options, &control) {
+        Err(partial) => {
+            assert!(matches!(partial.error, Error::TimeOut));
+            assert_eq!(partial.data.len(), 1);
+            assert_eq!(partial.data[0].ticker, "IBM US Equity");
+            assert_eq!(partial.data[0].data.px_last, Some(100.0));
+        }
+        Ok(rows) => panic!("Expected partial result, got {} rows", rows.len()),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_request_control_expired_deadline() -> Result<(), Error> {
+    let mut session = bdh_session()?;
+    let control = RequestControl::default().deadline(Instant::now());
+    let options = HistOptions::new("20250102", "20250102");
+    match session.bdh_with::<Price>(vec!["IBM US Equity"], options, &control) {
+        Err(partial) => {
+            assert!(matches!(partial.error, Error::TimeOut));
+            assert!(partial.data.is_empty());
+        }
+        Ok(rows) => panic!("Expected partial result, got {} rows", rows.len()),
+    }
+    Ok(())
+}
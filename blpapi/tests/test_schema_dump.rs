@@ -0,0 +1,45 @@
+use blpapi::{
+    constant::DataType,
+    schema_dump::{file_stem, ElementSummary, OperationSummary, ServiceSummary, UNBOUNDED},
+    service::BlpServices,
+};
+
+#[test]
+fn test_schema_dump_file_stem() {
+    assert_eq!(file_stem(&BlpServices::ReferenceData), "blp_refdata");
+    let custom = BlpServices::Custom(String::from("//mycompany/prices"));
+    assert_eq!(file_stem(&custom), "mycompany_prices");
+}
+
+#[test]
+fn test_schema_dump_json() {
+    let field = ElementSummary {
+        name: String::from("fields"),
+        type_name: String::from("String"),
+        data_type: DataType::BlpString,
+        min_values: 1,
+        max_values: UNBOUNDED,
+        ..Default::default()
+    };
+    let request = ElementSummary {
+        name: String::from("ReferenceDataRequest"),
+        elements: vec![field],
+        ..Default::default()
+    };
+    let summary = ServiceSummary {
+        name: String::from("//blp/refdata"),
+        description: String::from("Reference \"data\""),
+        operations: vec![OperationSummary {
+            name: String::from("ReferenceDataRequest"),
+            request: Some(request),
+            ..Default::default()
+        }],
+        events: vec![],
+    };
+    let json = summary.to_json();
+    assert!(json.contains("\"name\": \"//blp/refdata\""));
+    assert!(json.contains("Reference \\\"data\\\""));
+    assert!(json.contains("\"maxValues\": null"));
+    assert!(json.contains("\"minValues\": 1"));
+    assert!(json.contains("\"events\": []"));
+}
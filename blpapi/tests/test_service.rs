@@ -0,0 +1,37 @@
+use blpapi::{
+    core::{
+        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CORPORATE_EARNINGS,
+        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_PAGE_DATA, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_SOURCE_REF,
+    },
+    service::BlpServices,
+};
+
+#[test]
+fn test_service_identifiers() {
+    let page_data: &str = (&BlpServices::PageData).into();
+    assert_eq!(page_data, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_PAGE_DATA);
+    let earnings: &str = (&BlpServices::CorporateEarnings).into();
+    assert_eq!(
+        earnings,
+        BLPAPI_DEFAULT_SERVICE_IDENTIFIER_CORPORATE_EARNINGS
+    );
+}
+
+#[test]
+fn test_service_custom() {
+    let service = BlpServices::Custom(String::from("//blp/emapisvc"));
+    let id: &str = (&service).into();
+    assert_eq!(id, "//blp/emapisvc");
+}
+
+#[test]
+fn test_service_from_str() {
+    assert_eq!(
+        BlpServices::from(BLPAPI_DEFAULT_SERVICE_IDENTIFIER_SOURCE_REF),
+        BlpServices::SourceReference
+    );
+    assert_eq!(
+        BlpServices::from("//mycompany/prices"),
+        BlpServices::Custom(String::from("//mycompany/prices"))
+    );
+}
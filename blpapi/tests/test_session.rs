@@ -3,11 +3,131 @@ use blpapi::service::{BlpServiceStatus, BlpServices};
 use blpapi::{
     abstract_session::AbstractSession,
     core::{event_handler, BLPAPI_DEFAULT_SERVICE_IDENTIFIER_REFDATA},
+    element::Element,
+    event::{EventType, SlowConsumerPolicy, SubscriptionMsg, SubscriptionStream},
     event_dispatcher::EventDispatcherBuilder,
-    session::{EventHandler, Session, SessionBuilder},
+    recording::{EventReplay, RecordedEvent, RecordedMessage},
+    ref_data::RefData,
+    session::{EventHandler, Session, SessionBuilder, SessionCompletion, SubscriptionStatus},
     session_options::SessionOptions,
+    subscription_list::{Subscription, SubscriptionRegistry, TickerInfo},
+    time_series::{HistIntradayOptions, TickTypes},
     Error,
 };
+use chrono::NaiveDate;
+use std::sync::{Arc, Mutex};
+
+/// Schema of `//blp/refdata` reduced to the elements of a failed `ReferenceDataRequest`
+const REFDATA_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.refdata' version='1.0.0.0'>",
+    "<service name='//blp/refdata' version='1.0.0.0'>",
+    "<operation name='ReferenceDataRequest' serviceId='1'>",
+    "<request>ReferenceDataRequest</request>",
+    "<response>ReferenceDataResponse</response>",
+    "<responseSelection>ReferenceDataResponse</responseSelection>",
+    "</operation>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='ErrorInfo'>",
+    "<element name='source' type='String'/>",
+    "<element name='code' type='Int32'/>",
+    "<element name='category' type='String'/>",
+    "<element name='message' type='String'/>",
+    "<element name='subcategory' type='String' minOccurs='0'/>",
+    "</sequenceType>",
+    "<sequenceType name='ReferenceDataRequestType'>",
+    "<element name='securities' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='fields' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<sequenceType name='ReferenceDataResponseType'>",
+    "<element name='responseError' type='ErrorInfo' minOccurs='0'/>",
+    "<element name='securityData' type='String' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<element name='ReferenceDataRequest' type='ReferenceDataRequestType'/>",
+    "<element name='ReferenceDataResponse' type='ReferenceDataResponseType'/>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+const TICK_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.refdata' version='1.0.0.0'>",
+    "<service name='//blp/refdata' version='1.0.0.0'>",
+    "<operation name='IntradayTickRequest' serviceId='1'>",
+    "<request>IntradayTickRequest</request>",
+    "<response>IntradayTickResponse</response>",
+    "<responseSelection>IntradayTickResponse</responseSelection>",
+    "</operation>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='IntradayTickRequestType'>",
+    "<element name='security' type='String'/>",
+    "<element name='eventTypes' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='startDateTime' type='Datetime'/>",
+    "<element name='endDateTime' type='Datetime'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickDataType'>",
+    "<element name='time' type='Datetime'/>",
+    "<element name='type' type='String'/>",
+    "<element name='value' type='Float64'/>",
+    "<element name='size' type='Int32'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickDataArrayType'>",
+    "<element name='tickData' type='IntradayTickDataType' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickResponseType'>",
+    "<element name='tickData' type='IntradayTickDataArrayType'/>",
+    "</sequenceType>",
+    "<element name='IntradayTickRequest' type='IntradayTickRequestType'/>",
+    "<element name='IntradayTickResponse' type='IntradayTickResponseType'/>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+#[derive(Debug, Default)]
+struct Price {
+    px_last: Option<f64>,
+}
+
+impl RefData for Price {
+    const FIELDS: &'static [&'static str] = &["PX_LAST"];
+
+    fn on_field(&mut self, field: &str, element: &Element) {
+        if field == "PX_LAST" {
+            self.px_last = element.get_at(0);
+        }
+    }
+}
+
+/// `bdp` of a session replaying the event of a single message for the first request
+fn bdp_with_reply(
+    event_type: EventType,
+    message: RecordedMessage,
+) -> Result<Vec<blpapi::data_series::DataSeries<Price>>, Error> {
+    let event = RecordedEvent {
+        event_type,
+        messages: vec![message],
+    };
+    let recording = format!(
+        "{{\"service\":\"//blp/refdata\",\"schema\":\"{}\"}}\n{}\n",
+        REFDATA_SCHEMA,
+        event.to_line()?
+    );
+    let replay = EventReplay::from_reader(recording.as_bytes())?;
+    let mut session = SessionBuilder::default().replay(replay).build();
+    session.bdp::<Price>(vec!["IBM US Equity"], None, false, None)
+}
 
 fn start_session() -> Result<Session, Error> {
     let s_opt = SessionOptions::default();
@@ -51,6 +171,25 @@ fn test_session_get_service() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_session_service_operations() -> Result<(), Error> {
+    let mut s = start_session()?;
+    let serv = &BlpServices::ReferenceData;
+    s.open_service(serv)?;
+    let service = s.get_service(serv)?;
+    let names: Vec<String> = service.operations().map(|op| op.name()).collect();
+    assert!(names.contains(&String::from("ReferenceDataRequest")));
+    let operation = service.operation("ReferenceDataRequest")?;
+    let request = operation.request_definition()?;
+    assert_eq!(request.name.to_string(), "ReferenceDataRequest");
+    assert!(operation.num_response_definition() > 0);
+    assert_eq!(
+        service.event_definitions().count(),
+        service.num_event_definitions() as usize
+    );
+    Ok(())
+}
+
 #[test]
 fn test_session_create_identity() -> Result<(), Error> {
     let mut s = start_session()?;
@@ -78,6 +217,50 @@ fn test_session_with_option_handler() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_session_on_event() -> Result<(), Error> {
+    let s_opt = SessionOptions::default();
+    let disp = EventDispatcherBuilder::new(2).build();
+    let (tx, rx) = std::sync::mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    let mut s_async = SessionBuilder::default()
+        .options(s_opt)
+        .dispatcher(disp)
+        .on_event(move |mut event, _session| {
+            let _ = tx.lock().unwrap().send(event.event_type());
+        })
+        .build();
+    s_async.start()?;
+    let event_type = rx
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .map_err(|_| Error::TimeOut)?;
+    assert_eq!(event_type, EventType::SessionStatus);
+    drop(s_async);
+    Ok(())
+}
+
+#[test]
+fn test_session_start_async() -> Result<(), Error> {
+    let mut s = SessionBuilder::default()
+        .options(SessionOptions::default())
+        .build();
+    s.start_async()?;
+    assert!(s.is_pending());
+    let completions = s.wait_completions(std::time::Duration::from_secs(10))?;
+    assert!(matches!(completions[..], [SessionCompletion::Started]));
+    // The status events read while waiting are kept for the session
+    assert!(s.try_next_event().is_some());
+
+    let services = [BlpServices::ReferenceData, BlpServices::ApiFields];
+    let ids = s.open_services_async(&services)?;
+    assert_eq!(ids.len(), 2);
+    let completions = s.wait_completions(std::time::Duration::from_secs(10))?;
+    assert_eq!(completions.len(), 2);
+    assert!(!s.is_pending());
+    assert!(s.open_services.contains(&BlpServices::ApiFields));
+    Ok(())
+}
+
 #[test]
 fn test_session_create_request() -> Result<(), Error> {
     let mut s = start_session()?;
@@ -123,3 +306,229 @@ fn test_session_create_service_auth_name() -> Result<(), Error> {
     let name = service.authorization_name();
     Ok(())
 }
+
+#[test]
+fn test_session_slow_consumer_policy() -> Result<(), Error> {
+    let s = SessionBuilder::default().build();
+    assert_eq!(s.slow_consumer_policy, SlowConsumerPolicy::Deliver);
+    let s = SessionBuilder::default()
+        .slow_consumer_policy(SlowConsumerPolicy::LastValue)
+        .build();
+    assert_eq!(s.slow_consumer_policy, SlowConsumerPolicy::LastValue);
+    let key = s.subscription_key(&Subscription::new("BAYN GY Equity"));
+    assert_eq!(s.data_loss(&key), None);
+    Ok(())
+}
+
+#[test]
+fn test_session_subscription_status() {
+    let s = SessionBuilder::default().build();
+    let key = s.subscription_key(&Subscription::new("BAYN GY Equity"));
+    assert_eq!(s.subscription_status(&key), None);
+    let status = SubscriptionStatus::from(2u32);
+    assert_eq!(status, SubscriptionStatus::Subscribed);
+}
+
+/// Subscription status event of the subscription with correlation id 1
+fn status_event(message_type: &str, elements: &str) -> String {
+    let event = RecordedEvent {
+        event_type: EventType::SubscriptionStatus,
+        messages: vec![RecordedMessage {
+            message_type: message_type.to_string(),
+            correlation_ids: vec![1],
+            elements: elements.to_string(),
+            ..Default::default()
+        }],
+    };
+    event.to_line().unwrap()
+}
+
+#[test]
+fn test_session_subscription_failure_status() {
+    let reason = r#"{"reason":{"source":"src","errorCode":2,"category":"BAD_SEC","description":"Invalid security"}}"#;
+    let streams = r#"{"streams":[{"id":"1","endpoint":{"address":"localhost:8194"}}]}"#;
+    let recording = [
+        status_event("SubscriptionFailure", reason),
+        status_event("SubscriptionStreamsActivated", streams),
+    ]
+    .join("\n");
+    let replay = EventReplay::from_reader(recording.as_bytes()).unwrap();
+
+    let registry = SubscriptionRegistry::default();
+    let info = TickerInfo {
+        ticker: String::from("BAYN GY Equity"),
+        requested_fields: Default::default(),
+        service: BlpServices::MarketData,
+        topic_prefix: String::from("/ticker/"),
+        fields: vec![],
+        options: None,
+        data_loss: 0,
+        status: SubscriptionStatus::Subscribing,
+        reason: None,
+        streams: vec![],
+    };
+    registry.lock().unwrap().insert(1, info);
+    let stream = SubscriptionStream::<f64>::with_decoder(
+        std::ptr::null_mut(),
+        registry.clone(),
+        Box::new(|_, _| None),
+    )
+    .replay(Some(Arc::new(Mutex::new(replay))));
+    let statuses: Vec<SubscriptionMsg<f64>> = stream.map(Result::unwrap).collect();
+
+    match &statuses[..] {
+        [SubscriptionMsg::StatusChange {
+            status: SubscriptionStatus::Failed,
+            reason: Some(reason),
+            ..
+        }, SubscriptionMsg::StatusChange {
+            status: SubscriptionStatus::Failed,
+            streams,
+            ..
+        }] => {
+            assert_eq!(reason.category, "BAD_SEC");
+            assert_eq!(streams.len(), 1);
+        }
+        _ => panic!("Expected failure and stream status"),
+    }
+    let reg = registry.lock().unwrap();
+    let reason = reg[&1].reason.as_ref().unwrap();
+    assert_eq!(reason.description, "Invalid security");
+}
+
+#[test]
+fn test_session_subscription_key() {
+    let s = SessionBuilder::default().build();
+    let sub = Subscription::new("BAYN GY Equity");
+    let key = s.subscription_key(&sub);
+    assert_eq!(key.service, BlpServices::MarketData);
+    assert_eq!(key.topic_prefix, "/ticker/");
+    assert_eq!(key.ticker, "BAYN GY Equity");
+
+    let vwap = s.subscription_key(&sub.clone().service(BlpServices::Vwap));
+    let isin = s.subscription_key(&sub.topic_prefix("isin"));
+    assert_ne!(key, vwap);
+    assert_ne!(key, isin);
+    assert_eq!(isin.topic_prefix, "/isin/");
+}
+
+#[test]
+fn test_subscription_service() {
+    let sub = Subscription::new("BAYN GY Equity");
+    assert_eq!(sub.service, BlpServices::MarketData);
+    assert_eq!(sub.topic_prefix, None);
+    let sub = Subscription::default()
+        .service(BlpServices::MarketList)
+        .topic_prefix("/chain/");
+    assert_eq!(sub.service, BlpServices::MarketList);
+    assert_eq!(sub.topic_prefix.as_deref(), Some("/chain/"));
+}
+
+#[test]
+fn test_bdp_response_error() {
+    let message = RecordedMessage {
+        message_type: String::from("ReferenceDataResponse"),
+        service: Some(String::from("//blp/refdata")),
+        correlation_ids: vec![1],
+        elements: String::from(
+            r#"{"responseError":{"source":"bbdbd1","code":-4001,"category":"BAD_ARGS","message":"Invalid field"}}"#,
+        ),
+        ..Default::default()
+    };
+    match bdp_with_reply(EventType::Response, message) {
+        Err(Error::RequestFailure {
+            code,
+            category,
+            message,
+            ..
+        }) => {
+            assert_eq!(code, -4001);
+            assert_eq!(category, "BAD_ARGS");
+            assert_eq!(message, "Invalid field");
+        }
+        res => panic!("Expected request failure, got {:?}", res.map(|d| d.len())),
+    }
+}
+
+#[test]
+fn test_bdp_request_failure() {
+    let message = RecordedMessage {
+        message_type: String::from("RequestFailure"),
+        correlation_ids: vec![1],
+        elements: String::from(
+            r#"{"reason":{"source":"TSAM","errorCode":-1,"category":"TIMEOUT","description":"Request timed out"}}"#,
+        ),
+        ..Default::default()
+    };
+    match bdp_with_reply(EventType::RequestStatus, message) {
+        Err(Error::RequestFailure {
+            source,
+            category,
+            message,
+            ..
+        }) => {
+            assert_eq!(source, "TSAM");
+            assert_eq!(category, "TIMEOUT");
+            assert_eq!(message, "Request timed out");
+        }
+        res => panic!("Expected request failure, got {:?}", res.map(|d| d.len())),
+    }
+}
+
+/// Response of a window with trades at the given times
+fn tick_response(correlation_id: u64, times: &[&str]) -> RecordedEvent {
+    let ticks: Vec<String> = times
+        .iter()
+        .map(|time| {
+            format!(
+                r#"{{"time":"{}","type":"TRADE","value":100.0,"size":10}}"#,
+                time
+            )
+        })
+        .collect();
+    RecordedEvent {
+        event_type: EventType::Response,
+        messages: vec![RecordedMessage {
+            message_type: String::from("IntradayTickResponse"),
+            service: Some(String::from("//blp/refdata")),
+            correlation_ids: vec![correlation_id],
+            elements: format!(r#"{{"tickData":{{"tickData":[{}]}}}}"#, ticks.join(",")),
+            ..Default::default()
+        }],
+    }
+}
+
+#[test]
+fn test_bdib_window_cutoff() -> Result<(), Error> {
+    // both windows return the tick at the boundary, it belongs to the second one
+    let first = tick_response(1, &["2025-01-02T12:00:00.000", "2025-01-03T00:00:00.000"]);
+    let second = tick_response(2, &["2025-01-03T00:00:00.000", "2025-01-03T06:00:00.000"]);
+    let recording = format!(
+        "{{\"service\":\"//blp/refdata\",\"schema\":\"{}\"}}\n{}\n{}\n",
+        TICK_SCHEMA,
+        first.to_line()?,
+        second.to_line()?
+    );
+    let replay = EventReplay::from_reader(recording.as_bytes())?;
+    let mut session = SessionBuilder::default().replay(replay).build();
+    let options = HistIntradayOptions::new("20250102T000000", "20250104T000000")
+        .chunk(chrono::Duration::days(1));
+    let ticks = session.bdib(
+        String::from("IBM US Equity"),
+        vec![TickTypes::Trade],
+        options,
+    )?;
+
+    let day = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+    let next = day.succ_opt().unwrap();
+    let dates: Vec<_> = ticks.iter().map(|tick| tick.date).collect();
+    assert_eq!(
+        dates,
+        vec![
+            day.and_hms_opt(12, 0, 0).unwrap(),
+            next.and_hms_opt(0, 0, 0).unwrap(),
+            next.and_hms_opt(6, 0, 0).unwrap(),
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use blpapi::{
+    session_config::{AuthUserConfig, SessionConfig},
+    session_options::ClientMode,
+    Error,
+};
+
+#[test]
+fn test_session_config_from_vars() -> Result<(), Error> {
+    let vars = vec![
+        ("BLP_SERVERS", "primary:8194, backup:8196"),
+        ("BLP_CLIENT_MODE", "SAPI"),
+        ("BLP_CONNECT_TIMEOUT", "2500"),
+        ("BLP_AUTH_USER", "directory"),
+        ("BLP_AUTH_DIRECTORY_PROPERTY", "mail"),
+        ("BLP_AUTH_APPLICATION", "my-app"),
+        ("BLP_SOCKS5_HOST", "proxy"),
+        ("OTHER_CLIENT_MODE", "dapi"),
+    ];
+    let config = SessionConfig::from_vars("BLP", vars)?;
+    assert_eq!(
+        config.servers,
+        vec![
+            (String::from("primary"), 8194),
+            (String::from("backup"), 8196)
+        ]
+    );
+    assert_eq!(config.client_mode, Some(ClientMode::SApi));
+    assert_eq!(config.connect_timeout, Some(2500));
+    let auth = config.auth.expect("auth config");
+    assert_eq!(
+        auth.user,
+        Some(AuthUserConfig::ActiveDirectory(String::from("mail")))
+    );
+    assert_eq!(auth.application.as_deref(), Some("my-app"));
+    assert_eq!(config.socks5.map(|s| s.port), Some(1080));
+    assert_eq!(config.tls, None);
+    Ok(())
+}
+
+#[test]
+fn test_session_config_errors() {
+    let key_of = |vars: Vec<(&str, &str)>| match SessionConfig::from_vars("BLP", vars) {
+        Err(Error::Config { key, .. }) => key,
+        other => panic!("Expected a config error, got {:?}", other),
+    };
+    assert_eq!(key_of(vec![("BLP_SERVERS", "localhost")]), "servers");
+    assert_eq!(
+        key_of(vec![("BLP_CONNECT_TIMEOUT", "soon")]),
+        "connect_timeout"
+    );
+    assert_eq!(key_of(vec![("BLP_CLIENT_MODE", "remote")]), "client_mode");
+    assert_eq!(
+        key_of(vec![("BLP_AUTH_USER", "manual"), ("BLP_AUTH_USER_ID", "1")]),
+        "auth.ip_address"
+    );
+    assert_eq!(
+        key_of(vec![("BLP_TLS_CLIENT_CREDENTIALS", "client.pk12")]),
+        "tls.client_credentials_password"
+    );
+    assert_eq!(
+        key_of(vec![("BLP_AUTH_TOKEN", "t"), ("BLP_AUTH_APPLICATION", "a")]),
+        "auth.token"
+    );
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn test_session_config_from_toml() -> Result<(), Error> {
+    let config = SessionConfig::from_toml(
+        r#"
+        servers = ["localhost:8194"]
+        keep_alive = false
+        slow_consumer_warning_low_water_mark = 0.25
+        slow_consumer_warning_high_water_mark = 0.5
+
+        [tls]
+        client_credentials = "client.pk12"
+        client_credentials_password = "secret"
+        trusted_certificates = "root.pk7"
+        "#,
+    )?;
+    assert_eq!(config.servers, vec![(String::from("localhost"), 8194)]);
+    assert_eq!(config.keep_alive, Some(false));
+    assert_eq!(config.slow_consumer_warning_marks, Some((0.25, 0.5)));
+    assert_eq!(
+        config.tls.map(|tls| tls.client_credentials_password),
+        Some(String::from("secret"))
+    );
+
+    match SessionConfig::from_toml("[tls]\nclient_credential = \"client.pk12\"") {
+        Err(Error::Config { key, .. }) => assert_eq!(key, "tls.client_credential"),
+        other => panic!("Expected a config error, got {:?}", other),
+    }
+    Ok(())
+}
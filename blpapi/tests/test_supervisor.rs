@@ -0,0 +1,66 @@
+use blpapi::{
+    market_depth::{depth_subscription, BookType},
+    recording::EventReplay,
+    service::BlpServices,
+    session::SessionBuilder,
+    subscription_list::Subscription,
+    supervisor::ReconnectPolicy,
+    vwap::Vwap,
+    Error,
+};
+use std::time::Duration;
+
+#[test]
+fn test_reconnect_policy_default() {
+    let policy = ReconnectPolicy::default();
+    assert_eq!(policy.delay(1), Duration::from_millis(500));
+    assert_eq!(policy.delay(2), Duration::from_millis(1000));
+    assert!(policy.allows(100));
+}
+
+#[test]
+fn test_reconnect_policy_max_delay() {
+    let policy = ReconnectPolicy::default()
+        .initial_delay(Duration::from_secs(1))
+        .max_delay(Duration::from_secs(5))
+        .multiplier(3);
+    assert_eq!(policy.delay(2), Duration::from_secs(3));
+    assert_eq!(policy.delay(3), Duration::from_secs(5));
+    assert_eq!(policy.delay(64), Duration::from_secs(5));
+}
+
+#[test]
+fn test_reconnect_policy_max_attempts() {
+    let policy = ReconnectPolicy::default().max_attempts(2);
+    assert!(policy.allows(2));
+    assert!(!policy.allows(3));
+}
+
+#[test]
+fn test_replay_subscriptions() -> Result<(), Error> {
+    let replay = EventReplay::from_reader("".as_bytes())?;
+    let mut session = SessionBuilder::default().replay(replay).build();
+    let quote = Subscription::new("IBM US Equity");
+    let depth = depth_subscription("IBM US Equity", BookType::MarketByOrder);
+    session.subscribe::<Vwap>(vec![quote.clone(), depth.clone()])?;
+    let registry = session.registry.lock().unwrap().clone();
+
+    // subscriptions of every service are replayed with their correlation ids and options
+    assert_eq!(session.replay_subscriptions()?, 2);
+    let replayed = session.registry.lock().unwrap().clone();
+    assert_eq!(replayed.len(), registry.len());
+    for (sub, service) in [
+        (quote, BlpServices::MarketData),
+        (depth, BlpServices::MarketDepth),
+    ] {
+        let info = session
+            .ticker_info(&session.subscription_key(&sub))
+            .unwrap();
+        assert_eq!(info.service, service);
+        assert_eq!(info.fields.len(), sub.fields.len());
+        let options: Vec<String> = info.options.unwrap().into_iter().map(|o| o.value).collect();
+        let expected: Vec<String> = sub.options.unwrap().into_iter().map(|o| o.value).collect();
+        assert_eq!(options, expected);
+    }
+    Ok(())
+}
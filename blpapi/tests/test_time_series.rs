@@ -0,0 +1,37 @@
+use blpapi::time_series::HistIntradayOptions;
+use chrono::NaiveDate;
+
+#[test]
+fn test_hist_intraday_options_window() {
+    let options = HistIntradayOptions::new("20250102T093000", "2025-01-03");
+    let (start, end) = options.range().unwrap();
+    let day = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+    assert_eq!(start, day.and_hms_opt(9, 30, 0).unwrap());
+    assert_eq!(end, day.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap());
+
+    let noon = day.and_hms_opt(12, 0, 0).unwrap();
+    let options = options.window(noon, end);
+    assert_eq!(options.range(), Some((noon, end)));
+
+    let options = HistIntradayOptions::new("2025-01-02 T", "20250103");
+    assert_eq!(options.range(), None);
+    assert_eq!(options.data_points(), None);
+}
+
+#[test]
+fn test_hist_intraday_options_windows() {
+    let options = HistIntradayOptions::new("20250102T000000", "20250104T120000");
+    assert_eq!(options.windows().len(), 1);
+    assert_eq!(options.parallel_requests(), 1);
+
+    let options = options.chunk(chrono::Duration::days(1)).parallel(4);
+    assert_eq!(options.parallel_requests(), 4);
+    let windows = options.windows();
+    assert_eq!(windows.len(), 3);
+    let day = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+    let (start, end) = windows[1].range().unwrap();
+    assert_eq!(start, day.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap());
+    assert_eq!(end, windows[2].range().unwrap().0);
+    let (_, last_end) = windows[2].range().unwrap();
+    assert_eq!(last_end, options.range().unwrap().1);
+}
@@ -0,0 +1,248 @@
+use blpapi::{
+    element::Element,
+    event::EventType,
+    recording::{EventReplay, RecordedEvent, RecordedMessage},
+    ref_data::RefData,
+    session::{Session, SessionBuilder},
+    time_series::{HistIntradayOptions, HistOptions, TickTypes},
+    Error,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+
+const REFDATA_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.refdata' version='1.0.0.0'>",
+    "<service name='//blp/refdata' version='1.0.0.0'>",
+    "<operation name='HistoricalDataRequest' serviceId='1'>",
+    "<request>HistoricalDataRequest</request>",
+    "<response>HistoricalDataResponse</response>",
+    "<responseSelection>HistoricalDataResponse</responseSelection>",
+    "</operation>",
+    "<operation name='IntradayTickRequest' serviceId='2'>",
+    "<request>IntradayTickRequest</request>",
+    "<response>IntradayTickResponse</response>",
+    "<responseSelection>IntradayTickResponse</responseSelection>",
+    "</operation>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='HistoricalDataRequestType'>",
+    "<element name='securities' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='fields' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='startDate' type='String'/>",
+    "<element name='endDate' type='String'/>",
+    "</sequenceType>",
+    "<sequenceType name='HistoricalDataRowType'>",
+    "<element name='date' type='Date'/>",
+    "<element name='PX_LAST' type='Float64' minOccurs='0'/>",
+    "</sequenceType>",
+    "<sequenceType name='HistoricalSecurityDataType'>",
+    "<element name='security' type='String'/>",
+    "<element name='fieldData' type='HistoricalDataRowType' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<sequenceType name='HistoricalDataResponseType'>",
+    "<element name='securityData' type='HistoricalSecurityDataType'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickRequestType'>",
+    "<element name='security' type='String'/>",
+    "<element name='eventTypes' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='startDateTime' type='Datetime'/>",
+    "<element name='endDateTime' type='Datetime'/>",
+    "<element name='maxDataPoints' type='Int32' minOccurs='0'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickDataType'>",
+    "<element name='time' type='Datetime'/>",
+    "<element name='type' type='String'/>",
+    "<element name='value' type='Float64'/>",
+    "<element name='size' type='Int32'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickDataArrayType'>",
+    "<element name='tickData' type='IntradayTickDataType' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<sequenceType name='IntradayTickResponseType'>",
+    "<element name='tickData' type='IntradayTickDataArrayType'/>",
+    "</sequenceType>",
+    "<element name='HistoricalDataRequest' type='HistoricalDataRequestType'/>",
+    "<element name='HistoricalDataResponse' type='HistoricalDataResponseType'/>",
+    "<element name='IntradayTickRequest' type='IntradayTickRequestType'/>",
+    "<element name='IntradayTickResponse' type='IntradayTickResponseType'/>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+#[derive(Debug, Default)]
+struct Price {
+    px_last: Option<f64>,
+}
+
+impl RefData for Price {
+    const FIELDS: &'static [&'static str] = &["PX_LAST"];
+
+    fn on_field(&mut self, field: &str, element: &Element) {
+        if field == "PX_LAST" {
+            self.px_last = element.get_at(0);
+        }
+    }
+}
+
+fn response(event_type: EventType, message_type: &str, elements: String) -> RecordedEvent {
+    RecordedEvent {
+        event_type,
+        messages: vec![RecordedMessage {
+            message_type: message_type.to_string(),
+            service: Some(String::from("//blp/refdata")),
+            correlation_ids: vec![1],
+            elements,
+            ..Default::default()
+        }],
+    }
+}
+
+/// Response of an intraday tick request with trades at the given times of 2025-01-02
+fn tick_response(times: &[&str]) -> RecordedEvent {
+    let ticks: Vec<String> = times
+        .iter()
+        .map(|time| {
+            format!(
+                r#"{{"time":"2025-01-02T{}.000","type":"TRADE","value":100.0,"size":10}}"#,
+                time
+            )
+        })
+        .collect();
+    let elements = format!(r#"{{"tickData":{{"tickData":[{}]}}}}"#, ticks.join(","));
+    response(EventType::Response, "IntradayTickResponse", elements)
+}
+
+/// Session replaying the events in order, one per request or partial response
+fn replay_session(events: &[RecordedEvent]) -> Result<Session, Error> {
+    let mut recording = format!(
+        "{{\"service\":\"//blp/refdata\",\"schema\":\"{}\"}}\n",
+        REFDATA_SCHEMA
+    );
+    for event in events {
+        recording.push_str(&event.to_line()?);
+        recording.push('\n');
+    }
+    let replay = EventReplay::from_reader(recording.as_bytes())?;
+    Ok(SessionBuilder::default().replay(replay).build())
+}
+
+fn time(time: &str) -> NaiveDateTime {
+    let day = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+    day.and_time(time.parse().unwrap())
+}
+
+fn tick_times(
+    session: &mut Session,
+    options: HistIntradayOptions,
+) -> Vec<Result<NaiveDateTime, Error>> {
+    session
+        .bdib_iter(
+            String::from("IBM US Equity"),
+            vec![TickTypes::Trade],
+            options,
+        )
+        .map(|tick| tick.map(|tick| tick.date))
+        .collect()
+}
+
+#[test]
+fn test_bdib_iter_pages() -> Result<(), Error> {
+    // each full page continues from the second of its last tick
+    let mut session = replay_session(&[
+        tick_response(&["00:00:01", "00:00:02", "00:00:02"]),
+        tick_response(&["00:00:02", "00:00:02", "00:00:03"]),
+        tick_response(&["00:00:03", "00:00:05"]),
+    ])?;
+    let options = HistIntradayOptions::new("20250102T000000", "20250102T010000").max_data_points(3);
+    let times: Vec<NaiveDateTime> = tick_times(&mut session, options)
+        .into_iter()
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        times,
+        vec![
+            time("00:00:01"),
+            time("00:00:02"),
+            time("00:00:02"),
+            time("00:00:03"),
+            time("00:00:05"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bdib_iter_second_exceeds_page() -> Result<(), Error> {
+    let mut session = replay_session(&[tick_response(&["00:00:01", "00:00:01"])])?;
+    let options = HistIntradayOptions::new("20250102T000000", "20250102T010000").max_data_points(2);
+    let ticks = tick_times(&mut session, options);
+    assert_eq!(ticks.len(), 3);
+    assert!(ticks[..2].iter().all(Result::is_ok));
+    assert!(matches!(ticks[2], Err(Error::SessionOptionError { .. })));
+    Ok(())
+}
+
+#[test]
+fn test_bdib_iter_window_cutoff() -> Result<(), Error> {
+    // both windows return the tick at the boundary, it belongs to the second one
+    let mut session = replay_session(&[
+        tick_response(&["00:30:00", "01:00:00"]),
+        tick_response(&["01:00:00", "01:30:00"]),
+    ])?;
+    let options = HistIntradayOptions::new("20250102T000000", "20250102T020000")
+        .chunk(chrono::Duration::hours(1));
+    let times: Vec<NaiveDateTime> = tick_times(&mut session, options)
+        .into_iter()
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        times,
+        vec![time("00:30:00"), time("01:00:00"), time("01:30:00")]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bdh_iter_responses() -> Result<(), Error> {
+    let security = |ticker: &str, rows: &str| {
+        format!(
+            r#"{{"securityData":{{"security":"{}","fieldData":[{}]}}}}"#,
+            ticker, rows
+        )
+    };
+    let mut session = replay_session(&[
+        response(
+            EventType::PartialResponse,
+            "HistoricalDataResponse",
+            security(
+                "IBM US Equity",
+                r#"{"date":"2025-01-02","PX_LAST":100.0},{"date":"2025-01-03","PX_LAST":101.0}"#,
+            ),
+        ),
+        response(
+            EventType::Response,
+            "HistoricalDataResponse",
+            security("AAPL US Equity", r#"{"date":"2025-01-02","PX_LAST":200.0}"#),
+        ),
+    ])?;
+    let options = HistOptions::new("20250102", "20250103");
+    let rows = session
+        .bdh_iter::<Price>(vec!["IBM US Equity", "AAPL US Equity"], options)
+        .collect::<Result<Vec<_>, _>>()?;
+    let rows: Vec<(&str, Option<f64>)> = rows
+        .iter()
+        .map(|row| (row.ticker.as_str(), row.data.px_last))
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("IBM US Equity", Some(100.0)),
+            ("IBM US Equity", Some(101.0)),
+            ("AAPL US Equity", Some(200.0)),
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use blpapi::{
+    constant::DataType,
+    name::Name,
+    request::RequestTypes,
+    schema_dump::{ElementSummary, UNBOUNDED},
+    service::Service,
+    validation::{RequestValue, SchemaValidator, ValidationErrorKind},
+    Error,
+};
+
+fn element(name: &str, data_type: DataType, min: usize, max: usize) -> ElementSummary {
+    ElementSummary {
+        name: name.to_string(),
+        data_type,
+        min_values: min,
+        max_values: max,
+        ..Default::default()
+    }
+}
+
+fn ref_data_schema() -> ElementSummary {
+    let mut periodicity = element("periodicity", DataType::BlpEnumeration, 0, 1);
+    periodicity.enumeration = vec![String::from("DAILY"), String::from("WEEKLY")];
+    let mut overrides = element("overrides", DataType::BlpSequence, 0, UNBOUNDED);
+    overrides.elements = vec![
+        element("fieldId", DataType::BlpString, 1, 1),
+        element("value", DataType::BlpString, 1, 1),
+    ];
+    let mut request = element("ReferenceDataRequest", DataType::BlpSequence, 1, 1);
+    request.elements = vec![
+        element("securities", DataType::BlpString, 1, UNBOUNDED),
+        element("fields", DataType::BlpString, 1, UNBOUNDED),
+        element("returnEids", DataType::BlpBool, 0, 1),
+        periodicity,
+        overrides,
+    ];
+    request
+}
+
+fn field_override(field_id: RequestValue) -> RequestValue {
+    RequestValue::sequence(vec![("fieldId", field_id), ("value", "EUR".into())])
+}
+
+#[test]
+fn test_validation_valid_request() {
+    let validator = SchemaValidator::new(ref_data_schema());
+    let values = vec![
+        ("securities", vec!["IBM US Equity"].into()),
+        ("fields", vec!["PX_LAST", "NAME"].into()),
+        ("returnEids", true.into()),
+        ("periodicity", "DAILY".into()),
+        (
+            "overrides",
+            RequestValue::Array(vec![field_override("EQY_FUND_CRNCY".into())]),
+        ),
+    ];
+    assert!(validator.validate(&values).is_ok());
+}
+
+#[test]
+fn test_validation_errors_with_path() {
+    let validator = SchemaValidator::new(ref_data_schema());
+    let overrides = RequestValue::Array(vec![
+        field_override("A".into()),
+        field_override("B".into()),
+        field_override(1.into()),
+    ]);
+    let values = vec![
+        ("securities", vec!["IBM US Equity"].into()),
+        ("fields", RequestValue::Array(vec![])),
+        ("periodicity", "HOURLY".into()),
+        ("returnEid", true.into()),
+        ("overrides", overrides),
+    ];
+    let errors = validator.check(&values);
+    let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["fields", "periodicity", "returnEid", "overrides[2].fieldId"]
+    );
+    assert_eq!(
+        errors[0].kind,
+        ValidationErrorKind::TooFewValues { min: 1, found: 0 }
+    );
+    assert_eq!(errors[2].kind, ValidationErrorKind::UnknownElement);
+    assert_eq!(
+        errors[3].kind,
+        ValidationErrorKind::WrongType {
+            expected: DataType::BlpString,
+            found: String::from("int"),
+        }
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "periodicity: 'HOURLY' is not one of [DAILY, WEEKLY]"
+    );
+}
+
+#[test]
+fn test_validation_missing_required() {
+    let validator = SchemaValidator::new(ref_data_schema());
+    let values = vec![("securities", vec!["IBM US Equity"].into())];
+    match validator.validate(&values) {
+        Err(Error::Validation(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].path, "fields");
+        }
+        _ => panic!("Expected validation error"),
+    }
+}
+
+/// Schema of a test service with a reference data operation
+const TEST_SCHEMA: &str = concat!(
+    "<ServiceDefinition name='blp.test' version='1.0.0.0'>",
+    "<service name='//blp/test' version='1.0.0.0'>",
+    "<operation name='ReferenceDataRequest' serviceId='1'>",
+    "<request>ReferenceDataRequest</request>",
+    "<response>ReferenceDataResponse</response>",
+    "<responseSelection>ReferenceDataResponse</responseSelection>",
+    "</operation>",
+    "<defaultServiceId>1</defaultServiceId>",
+    "<publisherSupportsRecap>false</publisherSupportsRecap>",
+    "<authoritativeSourceSupportsRecap>false</authoritativeSourceSupportsRecap>",
+    "<isInfrastructureService>false</isInfrastructureService>",
+    "<isMetered>false</isMetered>",
+    "<appendMtrId>false</appendMtrId>",
+    "</service>",
+    "<schema>",
+    "<sequenceType name='Override'>",
+    "<element name='fieldId' type='String'/>",
+    "<element name='value' type='String'/>",
+    "</sequenceType>",
+    "<sequenceType name='ReferenceDataRequestType'>",
+    "<element name='securities' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='fields' type='String' minOccurs='1' maxOccurs='unbounded'/>",
+    "<element name='overrides' type='Override' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<sequenceType name='ReferenceDataResponseType'>",
+    "<element name='securityData' type='String' minOccurs='0' maxOccurs='unbounded'/>",
+    "</sequenceType>",
+    "<element name='ReferenceDataRequest' type='ReferenceDataRequestType'/>",
+    "<element name='ReferenceDataResponse' type='ReferenceDataResponseType'/>",
+    "</schema>",
+    "</ServiceDefinition>",
+);
+
+#[test]
+fn test_validation_request_tree() -> Result<(), Error> {
+    let service = Service::deserialize(TEST_SCHEMA)?;
+    let mut request = service.create_request(RequestTypes::ReferenceData)?;
+    request.append_named(&Name::new("securities"), "IBM US Equity")?;
+    let overrides = Name::new("overrides");
+    for field_id in ["A", "B"] {
+        let mut field_override = request.append_complex(&overrides)?;
+        field_override.set("fieldId", field_id)?;
+        field_override.set("value", "EUR")?;
+    }
+    let mut field_override = request.append_complex(&overrides)?;
+    field_override.set("value", "EUR")?;
+
+    let errors = request.validator().check_request(&request);
+    let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, vec!["fields", "overrides[2].fieldId"]);
+    assert!(matches!(request.validate(), Err(Error::Validation(_))));
+
+    request.append_named(&Name::new("fields"), "PX_LAST")?;
+    let errors = request.validator().check_request(&request);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, "overrides[2].fieldId");
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use blpapi::{vwap::VwapSubscription, RefData};
+use chrono::NaiveTime;
+
+#[test]
+fn test_vwap_subscription_options() {
+    let vwap = VwapSubscription::new("IBM US Equity")
+        .start_time(NaiveTime::from_hms_opt(14, 30, 0).unwrap())
+        .end_time(NaiveTime::from_hms_opt(21, 0, 0).unwrap())
+        .min_size(100)
+        .exclude_cond_code("OR")
+        .exclude_cond_code("PR");
+    let sub = vwap.build();
+    assert_eq!(sub.ticker, "IBM US Equity");
+    let options: Vec<String> = sub.options.unwrap().into_iter().map(|o| o.value).collect();
+    assert_eq!(
+        options,
+        vec![
+            "VWAP_START_TIME=14:30",
+            "VWAP_END_TIME=21:00",
+            "VWAP_MIN_SIZE=100",
+            "VWAP_EXCLUDE_COND_CODES=OR,PR",
+        ]
+    );
+}
+
+#[test]
+fn test_vwap_subscription_topic() {
+    let vwap = VwapSubscription::new("IBM US Equity")
+        .fields(vec!["RT_VWAP"])
+        .min_price(10.5);
+    assert_eq!(
+        vwap.topic(),
+        "//blp/mktvwap/ticker/IBM US Equity?fields=RT_VWAP&VWAP_MIN_PX=10.5"
+    );
+
+    let vwap = VwapSubscription::new("US4592001014")
+        .fields(vec!["RT_VWAP"])
+        .topic_prefix("/isin/");
+    assert_eq!(vwap.build().topic_prefix.as_deref(), Some("/isin/"));
+    assert_eq!(
+        vwap.topic(),
+        "//blp/mktvwap/isin/US4592001014?fields=RT_VWAP"
+    );
+}
+
+#[test]
+fn test_vwap_fields() {
+    assert!(blpapi::vwap::Vwap::FIELDS.contains(&"RT_VWAP"));
+}